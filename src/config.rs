@@ -1,132 +1,311 @@
-use std::{cmp, convert::TryInto, rc::Rc};
-
-use anyhow::Result;
-use ordered_float::OrderedFloat;
-use serde::__private::de;
-
-pub type SharedConfig = Rc<Config>;
-
-#[derive(Debug, Clone, Copy)]
-pub struct ConfigVar {
-    name: &'static str,
-    val: i32,
-    min: i32,
-    max: i32,
-}
-
-impl ConfigVar {
-    pub fn new(name: &'static str, val: i32, min: i32, max: i32) -> Self {
-        Self {
-            name,
-            val,
-            min,
-            max,
-        }
-    }
-
-    pub fn set(&mut self, val: i32) {
-        if val <= self.max && val >= self.min {
-            self.val = val;
-        } else if val <= self.min {
-            self.val = self.min;
-        } else {
-            self.val = self.max;
-        }
-    }
-
-    pub fn incr(&mut self) {
-        self.set(self.val + 1);
-    }
-
-    pub fn decr(&mut self) {
-        let amount = (self.max - self.min) / 10;
-        self.set(self.val - 1);
-    }
-
-    pub fn val(&self) -> i32 {
-        self.val
-    }
-
-    pub fn min(&self) -> i32 {
-        self.min
-    }
-
-    pub fn max(&self) -> i32 {
-        self.max
-    }
-
-    pub fn name(&self) -> &str {
-        self.name
-    }
-}
-
-pub const parameter_desc: [[&str; 2]; 8] = [
-    ["Anthill height", "Sets the anthill height. This parameter does not affect the simulation."],
-    ["Anthill width", "Sets the anthill width. This parameter does not affect the simulation."],
-    ["Max seeker steps", "Maximal steps a seeker ant wanders around, searching for food. After the max is reached the and returns back home."],
-    ["Minimal pheromone concentration", "Pheromone concentratin mandatory (on the tiles surrounding the nest) to spawn follower ants."],
-    ["Map width", "Sets the map width."],
-    ["Map height", "Sets the map height."],
-    ["Pheromone evaporation amount (per step)", "Amount of pheromone that evaporates from each tile each timestep."],
-    ["Pheromone drop", "Amount of pheromone, droped by an returning and (with food)."],
-];
-#[derive(Debug, Clone, Copy)]
-pub struct Config {
-    // stat bounds
-    // environment: Environment,
-    pub anthill_height: ConfigVar,
-    pub anthill_width: ConfigVar,
-    pub max_steps: ConfigVar,
-    pub min_ph_c: ConfigVar,
-    pub map_width: ConfigVar,
-    pub map_height: ConfigVar,
-    pub evaporation_rate: ConfigVar,
-    pub ph_drop: ConfigVar,
-}
-
-impl Default for Config {
-    fn default() -> Self {
-        Self {
-            anthill_height: ConfigVar::new("Anthill height", 3, 1, 7),
-            anthill_width: ConfigVar::new("Anthill width", 5, 3, 15),
-            max_steps: ConfigVar::new("Max steps", 100, 0, 1000),
-            min_ph_c: ConfigVar::new("Min Ph C", 10, 0, 90),
-            map_width: ConfigVar::new("Map width", 115, 50, 350),
-            map_height: ConfigVar::new("Map height", 46, 25, 200),
-            evaporation_rate: ConfigVar::new("Evaporation rate (in %)", 2, 0, 25),
-            ph_drop: ConfigVar::new("Ph drop", 79, 0, 100),
-        }
-    }
-}
-
-impl Config {
-    pub fn init() -> Self {
-        Self::default()
-    }
-
-    pub fn vars(&self) -> Vec<ConfigVar> {
-        vec![
-            self.anthill_height,
-            self.anthill_width,
-            self.max_steps,
-            self.min_ph_c,
-            self.map_width,
-            self.map_height,
-            self.evaporation_rate,
-            self.ph_drop,
-        ]
-    }
-
-    pub fn vars_mut(&mut self) -> Vec<&mut ConfigVar> {
-        vec![
-            &mut self.anthill_height,
-            &mut self.anthill_width,
-            &mut self.max_steps,
-            &mut self.min_ph_c,
-            &mut self.map_width,
-            &mut self.map_height,
-            &mut self.evaporation_rate,
-            &mut self.ph_drop,
-        ]
-    }
-}
+use std::{cmp, convert::TryInto, rc::Rc};
+
+use anyhow::Result;
+use ordered_float::OrderedFloat;
+
+use crate::core::map::map::MAX_COLONIES;
+
+pub type SharedConfig = Rc<Config>;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ConfigVar {
+    name: &'static str,
+    val: i32,
+    min: i32,
+    max: i32,
+    /// Applied to a running simulation right away instead of on reset.
+    live: bool,
+}
+
+impl ConfigVar {
+    pub fn new(name: &'static str, val: i32, min: i32, max: i32) -> Self {
+        Self {
+            name,
+            val,
+            min,
+            max,
+            live: false,
+        }
+    }
+
+    /// Marks a behavioural parameter, which a running simulation picks up immediately.
+    pub fn live(mut self) -> Self {
+        self.live = true;
+        self
+    }
+
+    pub fn set(&mut self, val: i32) {
+        if val <= self.max && val >= self.min {
+            self.val = val;
+        } else if val <= self.min {
+            self.val = self.min;
+        } else {
+            self.val = self.max;
+        }
+    }
+
+    pub fn incr(&mut self) {
+        self.set(self.val + 1);
+    }
+
+    pub fn decr(&mut self) {
+        let amount = (self.max - self.min) / 10;
+        self.set(self.val - 1);
+    }
+
+    pub fn val(&self) -> i32 {
+        self.val
+    }
+
+    pub fn min(&self) -> i32 {
+        self.min
+    }
+
+    pub fn max(&self) -> i32 {
+        self.max
+    }
+
+    pub fn name(&self) -> &str {
+        self.name
+    }
+
+    pub fn is_live(&self) -> bool {
+        self.live
+    }
+}
+
+pub const parameter_desc: [[&str; 2]; 41] = [
+    ["Anthill height", "Sets the anthill height. Returning ants arrive anywhere on the border of the nest. Applied on reset."],
+    ["Anthill width", "Sets the anthill width. Returning ants arrive anywhere on the border of the nest. Applied on reset."],
+    ["Max seeker steps", "Maximal steps a seeker ant wanders around, searching for food. After the max is reached the and returns back home."],
+    ["Minimal pheromone concentration", "Pheromone concentratin mandatory (on the tiles surrounding the nest) to spawn follower ants. Compared with the nest pheromone metric."],
+    ["Map width", "Sets the map width."],
+    ["Map height", "Sets the map height."],
+    ["Pheromone evaporation amount (per step)", "Amount of pheromone that evaporates from each tile each timestep."],
+    ["Pheromone drop", "Amount of pheromone, droped by an returning and (with food) on the trail to the food and by a seeker on the trail back home."],
+    ["Food per tile", "Amount of food each food tile holds. A tile disappears once all of its food has been carried away. Applied on reset."],
+    ["Food regrowth (per 1000 steps)", "Amount of food regrowing on each tile of a food source within 1000 steps, up to the initial amount. Depleted tiles grow back as well. Map files can set their own rate per area. Applied on reset."],
+    ["Map generator", "Generator used when no map file is loaded. 0: random food blocks, 1: scattered food clusters, 2: ring of food around the nest, 3: noise terrain with obstacles, 4: maze corridors. Applied on reset."],
+    ["Seed", "Seed of the map generator. The same seed and parameters always generate the same map. Applied on reset."],
+    ["Colonies", "Number of colonies competing for the food of a generated map, each with its own nest and pheromone. Map files define one colony per nest. Applied on reset."],
+    ["Boundary", "Behaviour at the map edges. 0: ants stop at the edge, 1: the map wraps around like a torus, 2: ants bounce off the edge. Applied on reset."],
+    ["Nest entrances", "Number of entrances in the middle of the nest sides (bottom, top, left, right). Ants leave the nest through a random entrance and the pheromone around the entrances decides whether they follow a trail. Applied on reset."],
+    ["Food piles", "Number of food blocks placed by the random blocks generator. Fewer piles are placed if the map has no room left for them. Applied on reset."],
+    ["Pile width", "Width of a food block of the random blocks generator. Applied on reset."],
+    ["Pile height", "Height of a food block of the random blocks generator. Applied on reset."],
+    ["Nest exclusion radius", "Food blocks of the random blocks generator start at least this many tiles away from every nest, horizontally and vertically. Applied on reset."],
+    ["Pheromone diffusion (per step)", "Percentage of the pheromone on a tile that spreads evenly onto the 8 surrounding tiles each timestep, widening the trails. Obstacles take up no pheromone."],
+    ["Evaporation model", "How pheromones evaporate. 0: the evaporation rate in percent evaporates each step, 1: a fixed amount (evaporation decay) evaporates each step, 2: the concentration halves within the half-life."],
+    ["Evaporation decay (per step)", "Amount of pheromone that evaporates from each tile each timestep with the linear evaporation model."],
+    ["Half-life", "Timesteps within which the pheromone on a tile halves with the half-life evaporation model."],
+    ["Pheromone threshold", "Pheromone below this concentration is removed from a tile. Lower it for small pheromone drops, traces below 0.1 are always removed."],
+    ["Pheromone cap", "Highest pheromone concentration a tile can hold, further drops are lost."],
+    ["Nest pheromone metric", "How the food trail pheromone around the nest entrances is summed up before it is compared with the minimal pheromone concentration. 0: sum, 1: mean, 2: maximum over all tiles within the nest pheromone radius."],
+    ["Nest pheromone radius", "Tiles around the nest entrances taken into account by the nest pheromone metric."],
+    ["Movement", "How seekers walk. 0: up, down, left and right, turning by 90°, 1: diagonals as well, turning by 45°, 2: a continuous heading turning by small angles, walking in the closest of the eight directions."],
+    ["Turn probability (in %)", "Chance of a seeker to turn each timestep."],
+    ["Turn angle", "Largest angle in degrees a seeker turns by with the continuous movement. Small angles give long, gently curving walks."],
+    ["Pheromone weight alpha (x0.1)", "Followers move to a neighbour with a chance proportional to concentration^alpha * heuristic^beta. Higher values make followers stick to the strongest trail, 0 ignores the concentration."],
+    ["Heuristic weight beta (x0.1)", "Weight of the heuristic in the follower rule, which prefers steps leading away from the nest. 0 ignores the heuristic."],
+    ["Exploration rate (in %)", "Chance of a follower to ignore the trail for one step and walk to any neighbour leading away from the nest."],
+    ["Homing mode", "How returners and noobs find back to the nest. 0: follow the exploration pheromone or head straight home, 1: walk the path the ant remembers from its way out backwards, 2: head for where the ant's integrated displacement places the nest (path integration)."],
+    ["Loop removal", "1: ants cut loops out of the remembered path whenever they come back to a tile, so they walk home the shortest part of their route. 0: the whole route is walked back."],
+    ["Path integration noise (in %)", "Error of every step an ant adds to its displacement from the nest, relative to the step length. The homing error of path integration grows with the length of the trip. Ants that miss the nest look for it close to where they expected it."],
+    ["Ant energy", "Timesteps an ant lasts without being fed. Ants eat from the food stored in the nest whenever they pass it and turn back home once their energy only lasts for the way back. 0: ants never starve."],
+    ["Food energy", "Energy a piece of stored food gives. Ants only eat once they can use up a whole piece."],
+    ["Max age", "Timesteps an ant lives, 0 lets ants live until they starve."],
+    ["Ant cost", "Stored food the nest spends to raise a new ant, at most one per timestep. 0: no ants are raised."],
+    ["Food reserve", "Stored food the nest keeps to feed its ants before it raises new ones."],
+];
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    // stat bounds
+    // environment: Environment,
+    pub anthill_height: ConfigVar,
+    pub anthill_width: ConfigVar,
+    pub max_steps: ConfigVar,
+    pub min_ph_c: ConfigVar,
+    pub map_width: ConfigVar,
+    pub map_height: ConfigVar,
+    pub evaporation_rate: ConfigVar,
+    pub ph_drop: ConfigVar,
+    pub food_amount: ConfigVar,
+    pub food_regrowth: ConfigVar,
+    pub generator: ConfigVar,
+    pub seed: ConfigVar,
+    pub colonies: ConfigVar,
+    pub boundary: ConfigVar,
+    pub nest_entrances: ConfigVar,
+    pub food_piles: ConfigVar,
+    pub pile_width: ConfigVar,
+    pub pile_height: ConfigVar,
+    pub nest_exclusion: ConfigVar,
+    pub diffusion: ConfigVar,
+    pub evaporation_model: ConfigVar,
+    pub evaporation_decay: ConfigVar,
+    pub half_life: ConfigVar,
+    pub ph_threshold: ConfigVar,
+    pub ph_cap: ConfigVar,
+    pub nest_ph_metric: ConfigVar,
+    pub nest_ph_radius: ConfigVar,
+    pub movement: ConfigVar,
+    pub turn_probability: ConfigVar,
+    pub turn_angle: ConfigVar,
+    pub alpha: ConfigVar,
+    pub beta: ConfigVar,
+    pub exploration_rate: ConfigVar,
+    pub homing: ConfigVar,
+    pub loop_removal: ConfigVar,
+    pub integration_noise: ConfigVar,
+    pub ant_energy: ConfigVar,
+    pub food_energy: ConfigVar,
+    pub max_age: ConfigVar,
+    pub ant_cost: ConfigVar,
+    pub food_reserve: ConfigVar,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            anthill_height: ConfigVar::new("Anthill height", 3, 1, 7),
+            anthill_width: ConfigVar::new("Anthill width", 5, 3, 15),
+            max_steps: ConfigVar::new("Max steps", 100, 0, 1000).live(),
+            min_ph_c: ConfigVar::new("Min Ph C", 10, 0, 90).live(),
+            map_width: ConfigVar::new("Map width", 115, 50, 350),
+            map_height: ConfigVar::new("Map height", 46, 25, 200),
+            evaporation_rate: ConfigVar::new("Evaporation rate (in %)", 2, 0, 25).live(),
+            ph_drop: ConfigVar::new("Ph drop", 79, 0, 100).live(),
+            food_amount: ConfigVar::new("Food per tile", 5, 1, 50),
+            food_regrowth: ConfigVar::new("Food regrowth", 0, 0, 100),
+            generator: ConfigVar::new("Map generator", 0, 0, 4),
+            seed: ConfigVar::new("Seed", 0, 0, 1000),
+            colonies: ConfigVar::new("Colonies", 1, 1, MAX_COLONIES as i32),
+            boundary: ConfigVar::new("Boundary", 0, 0, 2),
+            nest_entrances: ConfigVar::new("Nest entrances", 1, 1, 4),
+            food_piles: ConfigVar::new("Food piles", 8, 0, 30),
+            pile_width: ConfigVar::new("Pile width", 20, 1, 100),
+            pile_height: ConfigVar::new("Pile height", 6, 1, 50),
+            nest_exclusion: ConfigVar::new("Nest exclusion radius", 10, 0, 50),
+            diffusion: ConfigVar::new("Diffusion (in %)", 0, 0, 50).live(),
+            evaporation_model: ConfigVar::new("Evaporation model", 0, 0, 2).live(),
+            evaporation_decay: ConfigVar::new("Evaporation decay", 2, 0, 100).live(),
+            half_life: ConfigVar::new("Half-life", 35, 1, 1000).live(),
+            ph_threshold: ConfigVar::new("Ph threshold", 30, 0, 100).live(),
+            ph_cap: ConfigVar::new("Ph cap", 2000, 100, 5000).live(),
+            nest_ph_metric: ConfigVar::new("Nest ph metric", 0, 0, 2).live(),
+            nest_ph_radius: ConfigVar::new("Nest ph radius", 1, 1, 10).live(),
+            movement: ConfigVar::new("Movement", 0, 0, 2).live(),
+            turn_probability: ConfigVar::new("Turn probability (in %)", 20, 0, 100).live(),
+            turn_angle: ConfigVar::new("Turn angle", 30, 0, 180).live(),
+            alpha: ConfigVar::new("Alpha (x0.1)", 10, 0, 50).live(),
+            beta: ConfigVar::new("Beta (x0.1)", 10, 0, 50).live(),
+            exploration_rate: ConfigVar::new("Exploration rate (in %)", 0, 0, 100).live(),
+            homing: ConfigVar::new("Homing mode", 0, 0, 2).live(),
+            loop_removal: ConfigVar::new("Loop removal", 1, 0, 1).live(),
+            integration_noise: ConfigVar::new("Path integration noise (in %)", 10, 0, 100).live(),
+            ant_energy: ConfigVar::new("Ant energy", 0, 0, 10000).live(),
+            food_energy: ConfigVar::new("Food energy", 250, 1, 1000).live(),
+            max_age: ConfigVar::new("Max age", 0, 0, 20000).live(),
+            ant_cost: ConfigVar::new("Ant cost", 0, 0, 100).live(),
+            food_reserve: ConfigVar::new("Food reserve", 20, 0, 1000).live(),
+        }
+    }
+}
+
+impl Config {
+    pub fn init() -> Self {
+        Self::default()
+    }
+
+    pub fn vars(&self) -> Vec<ConfigVar> {
+        vec![
+            self.anthill_height,
+            self.anthill_width,
+            self.max_steps,
+            self.min_ph_c,
+            self.map_width,
+            self.map_height,
+            self.evaporation_rate,
+            self.ph_drop,
+            self.food_amount,
+            self.food_regrowth,
+            self.generator,
+            self.seed,
+            self.colonies,
+            self.boundary,
+            self.nest_entrances,
+            self.food_piles,
+            self.pile_width,
+            self.pile_height,
+            self.nest_exclusion,
+            self.diffusion,
+            self.evaporation_model,
+            self.evaporation_decay,
+            self.half_life,
+            self.ph_threshold,
+            self.ph_cap,
+            self.nest_ph_metric,
+            self.nest_ph_radius,
+            self.movement,
+            self.turn_probability,
+            self.turn_angle,
+            self.alpha,
+            self.beta,
+            self.exploration_rate,
+            self.homing,
+            self.loop_removal,
+            self.integration_noise,
+            self.ant_energy,
+            self.food_energy,
+            self.max_age,
+            self.ant_cost,
+            self.food_reserve,
+        ]
+    }
+
+    pub fn vars_mut(&mut self) -> Vec<&mut ConfigVar> {
+        vec![
+            &mut self.anthill_height,
+            &mut self.anthill_width,
+            &mut self.max_steps,
+            &mut self.min_ph_c,
+            &mut self.map_width,
+            &mut self.map_height,
+            &mut self.evaporation_rate,
+            &mut self.ph_drop,
+            &mut self.food_amount,
+            &mut self.food_regrowth,
+            &mut self.generator,
+            &mut self.seed,
+            &mut self.colonies,
+            &mut self.boundary,
+            &mut self.nest_entrances,
+            &mut self.food_piles,
+            &mut self.pile_width,
+            &mut self.pile_height,
+            &mut self.nest_exclusion,
+            &mut self.diffusion,
+            &mut self.evaporation_model,
+            &mut self.evaporation_decay,
+            &mut self.half_life,
+            &mut self.ph_threshold,
+            &mut self.ph_cap,
+            &mut self.nest_ph_metric,
+            &mut self.nest_ph_radius,
+            &mut self.movement,
+            &mut self.turn_probability,
+            &mut self.turn_angle,
+            &mut self.alpha,
+            &mut self.beta,
+            &mut self.exploration_rate,
+            &mut self.homing,
+            &mut self.loop_removal,
+            &mut self.integration_noise,
+            &mut self.ant_energy,
+            &mut self.food_energy,
+            &mut self.max_age,
+            &mut self.ant_cost,
+            &mut self.food_reserve,
+        ]
+    }
+}
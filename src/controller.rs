@@ -1,76 +1,163 @@
-use std::{borrow::BorrowMut, rc::Rc};
-
-use anyhow::{private::new_adhoc, Result};
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use enum_index::{EnumIndex, IndexEnum};
-
-use crate::{
-    app::App,
-    config::{self, SharedConfig},
-    core::simulation::simulation::Simulation,
-    keys::{KeyConfig, SharedKeyConfig},
-    UI,
-};
-
-pub fn key_event(app: &mut App, ev: KeyEvent, ui: &mut UI) -> Result<()> {
-    if ev == KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL) {
-        app.should_quit = true;
-        return Ok(());
-    }
-
-    if ev == ui.key_config.tab_config
-        || ev == ui.key_config.tab_simulation
-        || ev == ui.key_config.tab_eval
-    {
-        ui.switch_tab(ev)?;
-    }
-
-    match ui.tab {
-        0 => simulation_tab(app, ev, ui)?,
-        1 => config_tab(app, ev, ui)?,
-        _ => (),
-    }
-
-    Ok(())
-}
-
-fn config_tab(app: &mut App, ev: KeyEvent, ui: &mut UI) -> Result<()> {
-    if ev == app.key_config.move_down {
-        let param_count = app.config.vars().len();
-        let new_index = (ui.config_tab.parameter.selection + param_count + 1) % param_count;
-        ui.config_tab.parameter.selection = new_index;
-    } else if ev == app.key_config.move_up {
-        let param_count = app.config.vars().len();
-        let new_index = (ui.config_tab.parameter.selection + param_count - 1) % param_count;
-        ui.config_tab.parameter.selection = new_index;
-    } else if ev == app.key_config.move_right {
-        let mut vars_mut = app.config.vars_mut();
-        if let Some(var) = vars_mut.get_mut(ui.config_tab.parameter.selection) {
-            var.incr();
-        }
-    } else if ev == app.key_config.move_left {
-        let mut vars_mut = app.config.vars_mut();
-        if let Some(var) = vars_mut.get_mut(ui.config_tab.parameter.selection) {
-            var.decr();
-        }
-    }
-    Ok(())
-}
-
-fn simulation_tab(app: &mut App, ev: KeyEvent, ui: &mut UI) -> Result<()> {
-    if ev == app.key_config.start_simulation {
-    } else if ev == app.key_config.step_simulation {
-    } else if ev == app.key_config.spawn_ant {
-        app.simulation.spwan_ant();
-    } else if ev == app.key_config.span_ant_bulk {
-        for _ in 0..10 {
-            app.simulation.spwan_ant();
-        }
-    } else if ev == app.key_config.reset_sim {
-        app.simulation.reset(app.config);
-    } else if ev == app.key_config.pause_sim {
-        app.simulation.paused = !app.simulation.paused;
-    }
-
-    Ok(())
-}
+use std::{borrow::BorrowMut, rc::Rc};
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use enum_index::{EnumIndex, IndexEnum};
+
+use crate::{
+    app::App,
+    config::{self, SharedConfig},
+    core::{map::map::TileType, simulation::simulation::Simulation},
+    keys::{KeyConfig, SharedKeyConfig},
+    ui::tabs::{
+        editor_tab::{EDITOR_ASCII_PATH, EDITOR_MAP_PATH},
+        simulation::SAVE_PATH,
+    },
+    UI,
+};
+
+pub fn key_event(app: &mut App, ev: KeyEvent, ui: &mut UI) -> Result<()> {
+    if ev == KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL) {
+        app.should_quit = true;
+        return Ok(());
+    }
+
+    if ev == ui.key_config.tab_config
+        || ev == ui.key_config.tab_simulation
+        || ev == ui.key_config.tab_eval
+        || ev == ui.key_config.tab_editor
+    {
+        ui.switch_tab(ev)?;
+    }
+
+    match ui.tab {
+        0 => simulation_tab(app, ev, ui)?,
+        1 => config_tab(app, ev, ui)?,
+        3 => editor_tab(app, ev, ui)?,
+        _ => (),
+    }
+
+    Ok(())
+}
+
+fn config_tab(app: &mut App, ev: KeyEvent, ui: &mut UI) -> Result<()> {
+    if ev == app.key_config.move_down {
+        let param_count = app.config.vars().len();
+        let new_index = (ui.config_tab.parameter.selection + param_count + 1) % param_count;
+        ui.config_tab.parameter.selection = new_index;
+    } else if ev == app.key_config.move_up {
+        let param_count = app.config.vars().len();
+        let new_index = (ui.config_tab.parameter.selection + param_count - 1) % param_count;
+        ui.config_tab.parameter.selection = new_index;
+    } else if ev == app.key_config.move_right {
+        let mut vars_mut = app.config.vars_mut();
+        if let Some(var) = vars_mut.get_mut(ui.config_tab.parameter.selection) {
+            var.incr();
+        }
+        app.simulation.apply_config(&app.config);
+    } else if ev == app.key_config.move_left {
+        let mut vars_mut = app.config.vars_mut();
+        if let Some(var) = vars_mut.get_mut(ui.config_tab.parameter.selection) {
+            var.decr();
+        }
+        app.simulation.apply_config(&app.config);
+    }
+    Ok(())
+}
+
+fn simulation_tab(app: &mut App, ev: KeyEvent, ui: &mut UI) -> Result<()> {
+    if ev == app.key_config.start_simulation {
+    } else if ev == app.key_config.step_simulation {
+    } else if ev == app.key_config.spawn_ant {
+        app.simulation.spwan_ant();
+    } else if ev == app.key_config.span_ant_bulk {
+        for _ in 0..10 {
+            app.simulation.spwan_ant();
+        }
+    } else if ev == app.key_config.reset_sim {
+        if let Err(e) = app.simulation.reset(app.config) {
+            log::error!("failed resetting simulation: {:#}", e);
+        }
+    } else if ev == app.key_config.pause_sim {
+        app.simulation.paused = !app.simulation.paused;
+    } else if ev == app.key_config.toggle_distances {
+        ui.simulation_tab.distances = !ui.simulation_tab.distances;
+    } else if ev == app.key_config.save_sim {
+        ui.simulation_tab.status = match app.simulation.save(SAVE_PATH) {
+            Ok(()) => format!("saved to {}", SAVE_PATH),
+            Err(e) => {
+                log::error!("failed saving simulation: {:#}", e);
+                format!("{:#}", e)
+            }
+        };
+    } else if ev == app.key_config.load_sim {
+        ui.simulation_tab.status = match Simulation::load(SAVE_PATH) {
+            Ok(simulation) => {
+                app.config = *simulation.config();
+                app.simulation = simulation;
+                format!("loaded {}, paused", SAVE_PATH)
+            }
+            Err(e) => {
+                log::error!("failed loading simulation: {:#}", e);
+                format!("{:#}", e)
+            }
+        };
+    }
+
+    Ok(())
+}
+
+fn editor_tab(app: &mut App, ev: KeyEvent, ui: &mut UI) -> Result<()> {
+    let map = &mut app.simulation.map;
+    let cursor = &mut ui.editor_tab.cursor;
+    if ev == app.key_config.move_up {
+        cursor.y = (cursor.y - 1).max(0);
+    } else if ev == app.key_config.move_down {
+        cursor.y = (cursor.y + 1).min(map.height() - 1);
+    } else if ev == app.key_config.move_left {
+        cursor.x = (cursor.x - 1).max(0);
+    } else if ev == app.key_config.move_right {
+        cursor.x = (cursor.x + 1).min(map.width() - 1);
+    } else if ev == app.key_config.paint_food {
+        map.paint(*cursor, TileType::Food, app.config.food_amount.val());
+    } else if ev == app.key_config.paint_obstacle {
+        map.paint(*cursor, TileType::Border, 0);
+    } else if ev == app.key_config.paint_empty {
+        map.paint(*cursor, TileType::Empty, 0);
+    } else if ev == app.key_config.paint_terrain {
+        // the cursor may lie outside a smaller map loaded since it last moved
+        if map.in_bounds(*cursor) {
+            let terrain = map.get(*cursor).terrain.next();
+            map.set_terrain(*cursor, terrain);
+        }
+    } else if ev == app.key_config.move_nest {
+        map.move_nest(ui.editor_tab.colony, *cursor);
+        // ants of the colony return to the moved nest
+        if let Some(colony) = app.simulation.colonies.get_mut(ui.editor_tab.colony) {
+            colony.nest = map.nests[ui.editor_tab.colony];
+        }
+    } else if ev == app.key_config.next_colony {
+        ui.editor_tab.colony = (ui.editor_tab.colony + 1) % map.nests.len();
+    } else if ev == app.key_config.save_map || ev == app.key_config.export_map {
+        let path = if ev == app.key_config.save_map {
+            EDITOR_MAP_PATH
+        } else {
+            EDITOR_ASCII_PATH
+        };
+        ui.editor_tab.status = match map.save(path) {
+            Ok(()) => {
+                // resetting reloads the edited map, the text export loses terrain, food amounts
+                // and schedules and is not reloaded
+                if ev == app.key_config.save_map {
+                    app.simulation.map_path = Some(path.into());
+                }
+                format!("saved to {}", path)
+            }
+            Err(e) => {
+                log::error!("failed saving map: {:#}", e);
+                format!("{:#}", e)
+            }
+        };
+    }
+    Ok(())
+}
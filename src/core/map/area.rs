@@ -1,143 +1,187 @@
-use meval::Expr;
-use serde::{de::Error, Deserialize, Deserializer, Serialize};
-use std::fmt::Debug;
-
-#[derive(Debug, Clone, Copy, Deserialize)]
-pub enum AreaType {
-    Anthill,
-    Food,
-    Obstacle,
-}
-
-#[derive(Debug, Clone, Deserialize)]
-pub enum BorderDirection {
-    Above,
-    Beneath,
-}
-
-#[derive(Deserialize)]
-pub struct AreaBorder {
-    #[serde(deserialize_with = "deserialize_function")]
-    pub func: Box<dyn Fn(f64) -> f64>,
-    pub direction: BorderDirection,
-}
-
-fn deserialize_function<'de, D>(data: D) -> Result<Box<dyn Fn(f64) -> f64>, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    let buf: String = String::deserialize(data)?;
-    let expr: Expr = buf.parse().unwrap();
-    let func = expr.bind("x");
-    match func {
-        Ok(f) => Ok(Box::new(f)),
-        Err(_) => Err(serde::de::Error::custom("Error deserializing function")),
-    }
-}
-
-impl AreaBorder {
-    pub fn match_direction(&self, f_y: f64, y: f64) -> bool {
-        match self.direction {
-            BorderDirection::Above => f_y <= y,
-            BorderDirection::Beneath => f_y >= y,
-        }
-    }
-
-    pub fn ff(&self, y: f64) -> f64 {
-        (self.func)(y)
-    }
-
-    pub fn in_area(&self, x: f64, y: f64) -> bool {
-        let f_y = self.ff(x);
-        self.match_direction(f_y, y)
-    }
-
-    pub fn in_area_scaled(&self, x: f64, y: f64, x_scale: f64, y_scale: f64) -> bool {
-        let f_y = self.ff(x / x_scale) * y_scale;
-        self.match_direction(f_y, y * y_scale)
-    }
-}
-
-#[derive(Deserialize)]
-pub struct Area {
-    pub area_type: AreaType,
-    pub borders: Vec<AreaBorder>,
-}
-
-impl Area {
-    pub fn new(functions: Vec<(&'static str, BorderDirection)>, area_type: AreaType) -> Self {
-        let mut borders: Vec<AreaBorder> = Vec::new();
-        for (func_str, direction) in functions {
-            let expr: Result<Expr, meval::Error> = func_str.parse();
-            match expr {
-                Ok(e) => match e.bind("x") {
-                    Ok(f) => {
-                        let border_area = AreaBorder {
-                            func: Box::new(f),
-                            direction,
-                        };
-                        borders.push(border_area);
-                    }
-                    Err(_) => (),
-                },
-                Err(_) => {}
-            };
-        }
-
-        Self { area_type, borders }
-    }
-
-    pub fn inside(&self, x: f64, y: f64, x_scale: f64, y_scale: f64) -> bool {
-        self.borders
-            .iter()
-            .all(|b| b.in_area_scaled(x, y, x_scale, y_scale))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use meval::Expr;
-
-    use super::{Area, AreaBorder, AreaType, BorderDirection};
-
-    #[test]
-    fn point_inside() {
-        let expr: Expr = "-0.05*(x-12)^2+6".parse().unwrap();
-        let func = expr.bind("x").unwrap();
-        let border_1 = AreaBorder {
-            func: Box::new(func),
-            direction: super::BorderDirection::Beneath,
-        };
-
-        let expr: Expr = "0.05*(x-12)^2+1".parse().unwrap();
-        let func = expr.bind("x").unwrap();
-        let border_2 = AreaBorder {
-            func: Box::new(func),
-            direction: super::BorderDirection::Above,
-        };
-
-        assert!(border_1.in_area(12., 5.));
-        assert!(border_2.in_area(12., 5.));
-
-        assert!(!border_1.in_area(12., 10.));
-        assert!(border_2.in_area(12., 10.));
-
-        assert!(border_1.in_area(12., 0.));
-        assert!(!border_2.in_area(12., 0.));
-    }
-
-    #[test]
-    fn inside_area() {
-        let area = Area::new(
-            vec![
-                ("-0.05*(x-12)^2+6", BorderDirection::Beneath),
-                ("0.05*(x-12)^2+1", BorderDirection::Above),
-            ],
-            AreaType::Food,
-        );
-
-        assert!(area.inside(12., 5., 1., 1.));
-        assert!(!area.inside(12., 10., 1., 1.));
-        assert!(!area.inside(12., 0., 1., 1.));
-    }
-}
+use meval::Expr;
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
+use std::fmt::Debug;
+
+use super::map::Terrain;
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub enum AreaType {
+    Anthill,
+    Food,
+    Obstacle,
+    /// Only sets the terrain of the tiles, keeping what is on them.
+    Terrain,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub enum BorderDirection {
+    Above,
+    Beneath,
+}
+
+#[derive(Deserialize)]
+pub struct AreaBorder {
+    #[serde(deserialize_with = "deserialize_function")]
+    pub func: Box<dyn Fn(f64) -> f64>,
+    pub direction: BorderDirection,
+}
+
+fn deserialize_function<'de, D>(data: D) -> Result<Box<dyn Fn(f64) -> f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let buf: String = String::deserialize(data)?;
+    let expr: Expr = buf.parse().map_err(|e| {
+        serde::de::Error::custom(format!("invalid border function '{}': {}", buf, e))
+    })?;
+    let func = expr.bind("x");
+    match func {
+        Ok(f) => Ok(Box::new(f)),
+        Err(e) => Err(serde::de::Error::custom(format!(
+            "invalid border function '{}': {}",
+            buf, e
+        ))),
+    }
+}
+
+impl AreaBorder {
+    pub fn match_direction(&self, f_y: f64, y: f64) -> bool {
+        match self.direction {
+            BorderDirection::Above => f_y <= y,
+            BorderDirection::Beneath => f_y >= y,
+        }
+    }
+
+    pub fn ff(&self, y: f64) -> f64 {
+        (self.func)(y)
+    }
+
+    pub fn in_area(&self, x: f64, y: f64) -> bool {
+        let f_y = self.ff(x);
+        self.match_direction(f_y, y)
+    }
+
+    /// Checks a tile position against the border function stretched by `x_scale` and `y_scale`.
+    /// Both `x` and `y` are map tile coordinates, only the function is scaled, so `y` is compared
+    /// unscaled with the stretched border.
+    pub fn in_area_scaled(&self, x: f64, y: f64, x_scale: f64, y_scale: f64) -> bool {
+        let f_y = self.ff(x / x_scale) * y_scale;
+        self.match_direction(f_y, y)
+    }
+}
+
+#[derive(Deserialize)]
+pub struct Area {
+    pub area_type: AreaType,
+    pub borders: Vec<AreaBorder>,
+    /// Food per tile of a food area, the config value is used if not set.
+    #[serde(default)]
+    pub amount: Option<i32>,
+    /// Food regrown per tile and step, the config value is used if not set.
+    #[serde(default)]
+    pub regrowth: Option<f32>,
+    /// Step at which the area appears.
+    #[serde(default)]
+    pub appear: Option<i32>,
+    /// Step at which the area disappears.
+    #[serde(default)]
+    pub vanish: Option<i32>,
+    /// Terrain of a terrain area.
+    #[serde(default)]
+    pub terrain: Terrain,
+}
+
+impl Area {
+    pub fn new(functions: Vec<(&'static str, BorderDirection)>, area_type: AreaType) -> Self {
+        let mut borders: Vec<AreaBorder> = Vec::new();
+        for (func_str, direction) in functions {
+            let expr: Result<Expr, meval::Error> = func_str.parse();
+            match expr {
+                Ok(e) => match e.bind("x") {
+                    Ok(f) => {
+                        let border_area = AreaBorder {
+                            func: Box::new(f),
+                            direction,
+                        };
+                        borders.push(border_area);
+                    }
+                    Err(_) => (),
+                },
+                Err(_) => {}
+            };
+        }
+
+        Self {
+            area_type,
+            borders,
+            amount: None,
+            regrowth: None,
+            appear: None,
+            vanish: None,
+            terrain: Terrain::Ground,
+        }
+    }
+
+    pub fn inside(&self, x: f64, y: f64, x_scale: f64, y_scale: f64) -> bool {
+        self.borders
+            .iter()
+            .all(|b| b.in_area_scaled(x, y, x_scale, y_scale))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use meval::Expr;
+
+    use super::{Area, AreaBorder, AreaType, BorderDirection};
+
+    #[test]
+    fn point_inside() {
+        let expr: Expr = "-0.05*(x-12)^2+6".parse().unwrap();
+        let func = expr.bind("x").unwrap();
+        let border_1 = AreaBorder {
+            func: Box::new(func),
+            direction: super::BorderDirection::Beneath,
+        };
+
+        let expr: Expr = "0.05*(x-12)^2+1".parse().unwrap();
+        let func = expr.bind("x").unwrap();
+        let border_2 = AreaBorder {
+            func: Box::new(func),
+            direction: super::BorderDirection::Above,
+        };
+
+        assert!(border_1.in_area(12., 5.));
+        assert!(border_2.in_area(12., 5.));
+
+        assert!(!border_1.in_area(12., 10.));
+        assert!(border_2.in_area(12., 10.));
+
+        assert!(border_1.in_area(12., 0.));
+        assert!(!border_2.in_area(12., 0.));
+
+        // stretched twice, the border lies at y = 12 at the tile x = 24
+        assert!(border_1.in_area_scaled(24., 11., 2., 2.));
+        assert!(!border_1.in_area_scaled(24., 13., 2., 2.));
+    }
+
+    #[test]
+    fn inside_area() {
+        let area = Area::new(
+            vec![
+                ("-0.05*(x-12)^2+6", BorderDirection::Beneath),
+                ("0.05*(x-12)^2+1", BorderDirection::Above),
+            ],
+            AreaType::Food,
+        );
+
+        assert!(area.inside(12., 5., 1., 1.));
+        assert!(!area.inside(12., 10., 1., 1.));
+        assert!(!area.inside(12., 0., 1., 1.));
+
+        // stretched to twice the size the area spans y = 2..12 at x = 24
+        assert!(area.inside(24., 10., 2., 2.));
+        assert!(!area.inside(24., 13., 2., 2.));
+        assert!(!area.inside(12., 10., 2., 2.));
+    }
+}
@@ -0,0 +1,193 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+};
+
+use super::map::{Map, Nest, Pos};
+
+/// Tiles still to be relaxed, closest first.
+type Frontier = BinaryHeap<Reverse<(u32, i32, i32)>>;
+
+/// Steps from every tile to the nest of a colony, walking around obstacles.
+/// Nest tiles are at zero, obstacles and tiles cut off from the nest have no distance.
+#[derive(Clone, Default)]
+pub struct DistanceField {
+    width: i32,
+    distances: Vec<Option<u32>>,
+}
+
+impl DistanceField {
+    /// Searches outwards from the nest over all passable tiles.
+    pub fn new(map: &Map, nest: &Nest) -> Self {
+        let mut field = Self {
+            width: *map.width(),
+            distances: vec![None; (map.width() * map.height()) as usize],
+        };
+        let mut frontier = Frontier::new();
+        for pos in nest.tiles().filter(|pos| map.in_bounds(*pos)) {
+            field.set(pos, Some(0));
+            frontier.push(Reverse((0, pos.x, pos.y)));
+        }
+        field.relax(map, frontier);
+        field
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<u32> {
+        self.distances
+            .get((pos.y * self.width + pos.x) as usize)
+            .copied()
+            .flatten()
+    }
+
+    fn set(&mut self, pos: Pos, distance: Option<u32>) {
+        self.distances[(pos.y * self.width + pos.x) as usize] = distance;
+    }
+
+    /// Updates the field after the tile at `pos` turned passable or impassable.
+    /// Only the tiles whose distance depends on it are searched again.
+    pub fn update(&mut self, map: &Map, pos: Pos) {
+        if map.passable(pos) {
+            self.open(map, pos);
+        } else {
+            self.close(map, pos);
+        }
+    }
+
+    fn open(&mut self, map: &Map, pos: Pos) {
+        // relaxing the neighbours reaches the opened tile and the diagonal steps between
+        // them it no longer blocks
+        let mut frontier = Frontier::new();
+        for tile in map.get_neightbours(pos) {
+            if let Some(distance) = self.get(tile.pos) {
+                frontier.push(Reverse((distance, tile.pos.x, tile.pos.y)));
+            }
+        }
+        self.relax(map, frontier);
+    }
+
+    fn close(&mut self, map: &Map, pos: Pos) {
+        let mut lost = Vec::new();
+        if self.get(pos).is_some() {
+            self.set(pos, None);
+            lost.push(pos);
+        }
+
+        // tiles no longer reached from a tile one step closer lose their distance, starting
+        // with the neighbours, which may also have been linked diagonally past the closed tile
+        let mut queue = VecDeque::new();
+        queue.push_back(pos);
+        while let Some(current) = queue.pop_front() {
+            for tile in map.get_neightbours(current) {
+                let distance = match self.get(tile.pos) {
+                    Some(distance) if distance > 0 => distance,
+                    _ => continue,
+                };
+                let supported = map.get_neightbours(tile.pos).iter().any(|t| {
+                    self.get(t.pos) == Some(distance - 1) && map.can_step(t.pos, tile.pos)
+                });
+                if !supported {
+                    self.set(tile.pos, None);
+                    lost.push(tile.pos);
+                    queue.push_back(tile.pos);
+                }
+            }
+        }
+
+        // and take it up again from the neighbours left
+        let mut frontier = Frontier::new();
+        for pos in lost.into_iter().filter(|pos| map.passable(*pos)) {
+            if let Some(distance) = self.closest_neighbour(map, pos) {
+                self.set(pos, Some(distance + 1));
+                frontier.push(Reverse((distance + 1, pos.x, pos.y)));
+            }
+        }
+        self.relax(map, frontier);
+    }
+
+    fn closest_neighbour(&self, map: &Map, pos: Pos) -> Option<u32> {
+        map.get_neightbours(pos)
+            .iter()
+            .filter(|t| map.can_step(t.pos, pos))
+            .filter_map(|t| self.get(t.pos))
+            .min()
+    }
+
+    /// Lowers the distance of the neighbours of every frontier tile, Dijkstra style.
+    fn relax(&mut self, map: &Map, mut frontier: Frontier) {
+        while let Some(Reverse((distance, x, y))) = frontier.pop() {
+            let pos = Pos::new(x, y);
+            if self.get(pos) != Some(distance) {
+                continue;
+            }
+            for tile in map.get_neightbours(pos) {
+                if map.can_step(pos, tile.pos)
+                    && self.get(tile.pos).is_none_or(|d| d > distance + 1)
+                {
+                    self.set(tile.pos, Some(distance + 1));
+                    frontier.push(Reverse((distance + 1, tile.pos.x, tile.pos.y)));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DistanceField;
+    use crate::{
+        config::Config,
+        core::map::map::{Map, Pos, TileType},
+    };
+
+    // the nest sits left of a wall, the right half is reached through the bottom row
+    const WALL_MAP: &str = "XXXXXXXXX\nX...X...X\nX.H.X...X\nX.......X\nXXXXXXXXX\n";
+
+    fn assert_fresh(map: &Map) {
+        let fresh = DistanceField::new(map, &map.colony_nests[0]);
+        for y in 0..*map.height() {
+            for x in 0..*map.width() {
+                let pos = Pos::new(x, y);
+                assert_eq!(
+                    map.nest_distance(0, pos),
+                    fresh.get(pos),
+                    "at ({}, {})",
+                    x,
+                    y
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn distance_field() {
+        let mut map = Map::from_ascii("Wall", WALL_MAP, &Config::default()).unwrap();
+        assert_eq!(map.nest_distance(0, Pos::new(2, 2)), Some(0));
+        assert_eq!(map.nest_distance(0, Pos::new(6, 1)), Some(4));
+        assert_eq!(map.nest_distance(0, Pos::new(4, 1)), None);
+
+        // closing the gap cuts off the right half
+        map.paint(Pos::new(4, 3), TileType::Border, 0);
+        assert_eq!(map.nest_distance(0, Pos::new(6, 1)), None);
+        assert_fresh(&map);
+
+        map.paint(Pos::new(4, 3), TileType::Empty, 0);
+        assert_eq!(map.nest_distance(0, Pos::new(6, 1)), Some(4));
+        assert_fresh(&map);
+
+        // a detour around a new obstacle
+        map.paint(Pos::new(3, 3), TileType::Border, 0);
+        map.paint(Pos::new(3, 2), TileType::Border, 0);
+        assert_fresh(&map);
+        map.paint(Pos::new(3, 2), TileType::Empty, 0);
+        assert_fresh(&map);
+
+        // no diagonal step between two obstacles touching at a corner
+        map.paint(Pos::new(3, 3), TileType::Empty, 0);
+        map.paint(Pos::new(5, 3), TileType::Border, 0);
+        assert_eq!(map.nest_distance(0, Pos::new(5, 2)), None);
+        assert_fresh(&map);
+        map.paint(Pos::new(5, 3), TileType::Empty, 0);
+        assert_eq!(map.nest_distance(0, Pos::new(5, 2)), Some(3));
+        assert_fresh(&map);
+    }
+}
@@ -0,0 +1,386 @@
+use std::{collections::VecDeque, f32::consts::PI};
+
+use enum_index::IndexEnum;
+use enum_index_derive::{EnumIndex, IndexEnum};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::config::Config;
+
+use super::map::{Map, Pos, Tile, TileType};
+
+/// Random placements a generator tries before giving up on a crowded map.
+const MAX_ATTEMPTS: i32 = 1000;
+
+/// Procedural map generators, selected via `Config::generator` and driven by `Config::seed`.
+/// The same seed and config always produce the same map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIndex, IndexEnum)]
+pub enum Generator {
+    /// Rectangular food blocks placed randomly away from the nests.
+    Blocks,
+    /// Round, frayed food clusters scattered over the map.
+    Clusters,
+    /// A broken ring of food around the map center, which holds the nest of a single colony.
+    Ring,
+    /// Value noise terrain, high values become obstacles, low values food.
+    Noise,
+    /// Maze corridors with food in the dead ends.
+    Maze,
+}
+
+impl Generator {
+    pub fn from_config(config: &Config) -> Self {
+        Generator::index_enum(config.generator.val() as usize).unwrap_or(Generator::Blocks)
+    }
+
+    pub fn generate(&self, map: &Map, config: &Config) -> Vec<Vec<Tile>> {
+        let mut rng = ChaCha8Rng::seed_from_u64(config.seed.val() as u64);
+        let food_amount = config.food_amount.val();
+        let mut tile_matrix = map.init_tiles();
+
+        match self {
+            Generator::Blocks => blocks(map, &mut tile_matrix, &mut rng, config),
+            Generator::Clusters => clusters(map, &mut tile_matrix, &mut rng, food_amount),
+            Generator::Ring => ring(map, &mut tile_matrix, &mut rng, food_amount),
+            Generator::Noise => noise(map, &mut tile_matrix, &mut rng, food_amount),
+            Generator::Maze => maze(map, &mut tile_matrix, &mut rng, food_amount),
+        }
+
+        // the nests always stay in place
+        for pos in map.colony_nests.iter().flat_map(|nest| nest.tiles()) {
+            tile_matrix[pos.y as usize][pos.x as usize] = Tile::nest(pos);
+        }
+
+        label_sources(&mut tile_matrix, 0);
+        tile_matrix
+    }
+}
+
+fn set(tile_matrix: &mut [Vec<Tile>], tile: Tile) {
+    tile_matrix[tile.pos.y as usize][tile.pos.x as usize] = tile;
+}
+
+fn blocks(map: &Map, tile_matrix: &mut [Vec<Tile>], rng: &mut ChaCha8Rng, config: &Config) {
+    let food_amount = config.food_amount.val();
+    let food_block_x = config.pile_width.val();
+    let food_block_y = config.pile_height.val();
+    let exclusion = config.nest_exclusion.val();
+    // the piles do not fit on the map
+    if food_block_x >= *map.width() || food_block_y >= *map.height() {
+        return;
+    }
+
+    let mut food_piles = 0;
+    let mut attempts = 0;
+    while food_piles < config.food_piles.val() && attempts < MAX_ATTEMPTS {
+        attempts += 1;
+
+        let rand_x = rng.gen_range(0..map.width() - food_block_x);
+        let rand_y = rng.gen_range(0..map.height() - food_block_y);
+
+        if map.nests.iter().all(|nest_pos| {
+            (rand_x < nest_pos.x - exclusion || rand_x > nest_pos.x + exclusion)
+                && (rand_y < nest_pos.y - exclusion || rand_y > nest_pos.y + exclusion)
+        }) {
+            for y in rand_y..rand_y + food_block_y {
+                for x in rand_x..rand_x + food_block_x {
+                    set(tile_matrix, Tile::food(x, y, food_amount, 0));
+                }
+            }
+            food_piles += 1;
+        }
+    }
+}
+
+fn clusters(map: &Map, tile_matrix: &mut [Vec<Tile>], rng: &mut ChaCha8Rng, food_amount: i32) {
+    let mut clusters = 0;
+    let mut attempts = 0;
+    while clusters < 8 && attempts < MAX_ATTEMPTS {
+        attempts += 1;
+
+        let radius = rng.gen_range(2..6);
+        let center = Pos::new(
+            rng.gen_range(0..*map.width()),
+            rng.gen_range(0..*map.height()),
+        );
+        if map.nest_dist(center) < (10 + radius) as f32 {
+            continue;
+        }
+
+        for y in center.y - radius..=center.y + radius {
+            for x in center.x - radius..=center.x + radius {
+                let pos = Pos::new(x, y);
+                let dist = map.dist(pos, center);
+                // denser in the middle, frayed at the edge
+                if map.in_bounds(pos)
+                    && dist <= radius as f32
+                    && rng.gen::<f32>() < 1. - dist / (radius + 1) as f32
+                {
+                    set(tile_matrix, Tile::food(x, y, food_amount, 0));
+                }
+            }
+        }
+        clusters += 1;
+    }
+}
+
+fn ring(map: &Map, tile_matrix: &mut [Vec<Tile>], rng: &mut ChaCha8Rng, food_amount: i32) {
+    const SEGMENTS: usize = 8;
+
+    let center = Pos::new(map.width() / 2, map.height() / 2);
+    let radius = (*map.width()).min(*map.height()) as f32 / 3.;
+    let mut filled: Vec<bool> = (0..SEGMENTS).map(|_| rng.gen_bool(0.75)).collect();
+    if !filled.iter().any(|f| *f) {
+        filled[rng.gen_range(0..SEGMENTS)] = true;
+    }
+
+    for y in 0..*map.height() {
+        for x in 0..*map.width() {
+            let pos = Pos::new(x, y);
+            if (map.dist(pos, center) - radius).abs() > 1. {
+                continue;
+            }
+
+            let angle = ((y - center.y) as f32).atan2((x - center.x) as f32);
+            let segment = ((angle + PI) / (2. * PI) * SEGMENTS as f32) as usize % SEGMENTS;
+            if filled[segment] {
+                set(tile_matrix, Tile::food(x, y, food_amount, 0));
+            }
+        }
+    }
+}
+
+fn noise(map: &Map, tile_matrix: &mut [Vec<Tile>], rng: &mut ChaCha8Rng, food_amount: i32) {
+    const CELL: i32 = 8;
+
+    let lattice: Vec<Vec<f32>> = (0..=map.height() / CELL + 1)
+        .map(|_| (0..=map.width() / CELL + 1).map(|_| rng.gen()).collect())
+        .collect();
+    let smooth = |t: f32| t * t * (3. - 2. * t);
+
+    for y in 0..*map.height() {
+        for x in 0..*map.width() {
+            let pos = Pos::new(x, y);
+            if map.nest_dist(pos) < 10. {
+                continue;
+            }
+
+            // bilinear interpolation between the surrounding lattice points
+            let (cx, cy) = ((x / CELL) as usize, (y / CELL) as usize);
+            let tx = smooth((x % CELL) as f32 / CELL as f32);
+            let ty = smooth((y % CELL) as f32 / CELL as f32);
+            let top = lattice[cy][cx] * (1. - tx) + lattice[cy][cx + 1] * tx;
+            let bottom = lattice[cy + 1][cx] * (1. - tx) + lattice[cy + 1][cx + 1] * tx;
+            let value = top * (1. - ty) + bottom * ty;
+
+            if value > 0.75 {
+                set(tile_matrix, Tile::border(x, y));
+            } else if value < 0.2 {
+                set(tile_matrix, Tile::food(x, y, food_amount, 0));
+            }
+        }
+    }
+}
+
+fn maze(map: &Map, tile_matrix: &mut [Vec<Tile>], rng: &mut ChaCha8Rng, food_amount: i32) {
+    // cells are 3x3 tiles of corridor surrounded by walls of one tile
+    const CELL: i32 = 4;
+
+    let cols = ((map.width() - 1) / CELL).max(1);
+    let rows = ((map.height() - 1) / CELL).max(1);
+    let cell_origin = |cx: i32, cy: i32| Pos::new(cx * CELL + 1, cy * CELL + 1);
+
+    for row in tile_matrix.iter_mut() {
+        for tile in row.iter_mut() {
+            *tile = Tile::border(tile.pos.x, tile.pos.y);
+        }
+    }
+
+    // recursive backtracker
+    let mut visited = vec![vec![false; cols as usize]; rows as usize];
+    let mut connections = vec![vec![0; cols as usize]; rows as usize];
+    let start = (
+        ((map.nests[0].x - 1) / CELL).clamp(0, cols - 1),
+        ((map.nests[0].y - 1) / CELL).clamp(0, rows - 1),
+    );
+    let mut stack = vec![start];
+    visited[start.1 as usize][start.0 as usize] = true;
+
+    let carve = |tile_matrix: &mut [Vec<Tile>], from: Pos, width: i32, height: i32| {
+        for y in from.y..from.y + height {
+            for x in from.x..from.x + width {
+                if map.in_bounds(Pos::new(x, y)) {
+                    set(tile_matrix, Tile::empty(x, y));
+                }
+            }
+        }
+    };
+
+    while let Some(&(cx, cy)) = stack.last() {
+        let origin = cell_origin(cx, cy);
+        carve(tile_matrix, origin, CELL - 1, CELL - 1);
+
+        let unvisited: Vec<(i32, i32)> = [(1, 0), (-1, 0), (0, 1), (0, -1)]
+            .iter()
+            .map(|(dx, dy)| (cx + dx, cy + dy))
+            .filter(|(nx, ny)| *nx >= 0 && *nx < cols && *ny >= 0 && *ny < rows)
+            .filter(|(nx, ny)| !visited[*ny as usize][*nx as usize])
+            .collect();
+
+        if unvisited.is_empty() {
+            stack.pop();
+            continue;
+        }
+
+        let (nx, ny) = unvisited[rng.gen_range(0..unvisited.len())];
+        // remove the wall between both cells
+        let next = cell_origin(nx, ny);
+        let from = Pos::new(origin.x.min(next.x), origin.y.min(next.y));
+        carve(
+            tile_matrix,
+            from,
+            (next.x - origin.x).abs() + CELL - 1,
+            (next.y - origin.y).abs() + CELL - 1,
+        );
+
+        visited[ny as usize][nx as usize] = true;
+        connections[cy as usize][cx as usize] += 1;
+        connections[ny as usize][nx as usize] += 1;
+        stack.push((nx, ny));
+    }
+
+    // the nests always lie within a corridor
+    for nest in map.colony_nests.iter() {
+        carve(
+            tile_matrix,
+            Pos::new(nest.min.x - 1, nest.min.y - 1),
+            nest.max.x - nest.min.x + 3,
+            nest.max.y - nest.min.y + 3,
+        );
+    }
+
+    // food in the dead ends
+    for cy in 0..rows {
+        for cx in 0..cols {
+            let origin = cell_origin(cx, cy);
+            if connections[cy as usize][cx as usize] == 1 && map.nest_dist(origin) >= 10. {
+                for y in origin.y..origin.y + CELL - 1 {
+                    for x in origin.x..origin.x + CELL - 1 {
+                        if map.in_bounds(Pos::new(x, y)) {
+                            set(tile_matrix, Tile::food(x, y, food_amount, 0));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Gives every connected patch of food its own source. Only food tiles with a source
+/// of at least `first` are relabeled, starting at `first`.
+pub(super) fn label_sources(tile_matrix: &mut [Vec<Tile>], first: usize) {
+    let height = tile_matrix.len() as i32;
+    let width = tile_matrix.first().map(|row| row.len()).unwrap_or(0) as i32;
+    let unlabeled = |t: &Tile| t.tile_type == TileType::Food && t.source.is_none_or(|s| s >= first);
+    let mut labeled = vec![vec![false; width as usize]; height as usize];
+    let mut source = first;
+
+    for y in 0..height {
+        for x in 0..width {
+            if labeled[y as usize][x as usize] || !unlabeled(&tile_matrix[y as usize][x as usize]) {
+                continue;
+            }
+
+            let mut queue = VecDeque::new();
+            queue.push_back((x, y));
+            labeled[y as usize][x as usize] = true;
+            while let Some((tx, ty)) = queue.pop_front() {
+                tile_matrix[ty as usize][tx as usize].source = Some(source);
+                for ny in ty - 1..=ty + 1 {
+                    for nx in tx - 1..=tx + 1 {
+                        if nx >= 0
+                            && nx < width
+                            && ny >= 0
+                            && ny < height
+                            && !labeled[ny as usize][nx as usize]
+                            && unlabeled(&tile_matrix[ny as usize][nx as usize])
+                        {
+                            labeled[ny as usize][nx as usize] = true;
+                            queue.push_back((nx, ny));
+                        }
+                    }
+                }
+            }
+            source += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use enum_index::IndexEnum;
+
+    use super::Generator;
+    use crate::{
+        config::Config,
+        core::map::map::{Map, TileType},
+    };
+
+    fn tile_types(map: &Map) -> Vec<TileType> {
+        map.tile_matrix
+            .iter()
+            .flatten()
+            .map(|t| t.tile_type)
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_same_map() {
+        let mut i = 0;
+        while let Some(generator) = Generator::index_enum(i) {
+            let mut config = Config::default();
+            config.generator.set(i as i32);
+            config.seed.set(42);
+
+            let map = Map::new("Map", &config);
+            assert_eq!(tile_types(&map), tile_types(&Map::new("Map", &config)));
+            assert!(
+                map.get(map.nests[0]).tile_type == TileType::Nest,
+                "{:?} removed the nest",
+                generator
+            );
+            assert!(
+                tile_types(&map).contains(&TileType::Food),
+                "{:?} placed no food",
+                generator
+            );
+
+            config.seed.set(43);
+            assert!(tile_types(&map) != tile_types(&Map::new("Map", &config)));
+            i += 1;
+        }
+        assert_eq!(i, 5);
+    }
+
+    #[test]
+    fn blocks_layout() {
+        let mut config = Config::default();
+        config.food_piles.set(1);
+        config.pile_width.set(4);
+        config.pile_height.set(3);
+        let map = Map::new("Map", &config);
+        let food = tile_types(&map)
+            .into_iter()
+            .filter(|t| *t == TileType::Food)
+            .count();
+        assert_eq!(food, 12);
+
+        // no room for food, the generator gives up
+        config.nest_exclusion.set(50);
+        let map = Map::new("Map", &config);
+        assert!(!tile_types(&map).contains(&TileType::Food));
+        config.pile_width.set(100);
+        let map = Map::new("Map", &config);
+        assert!(!tile_types(&map).contains(&TileType::Food));
+    }
+}
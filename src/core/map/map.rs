@@ -1,289 +1,1817 @@
-use getset::{Getters, MutGetters, Setters};
-use rand::Rng;
-use serde::{Deserialize, Serialize};
-
-use super::area::Area;
-
-#[derive(Getters, MutGetters, Setters, Deserialize, Default)]
-pub struct Map {
-    #[getset(get = "pub")]
-    name: String,
-    #[getset(get = "pub")]
-    food: Vec<Area>,
-    #[getset(get = "pub")]
-    width: i32,
-    #[getset(get = "pub")]
-    height: i32,
-    pub tile_matrix: Vec<Vec<Tile>>,
-    pub nest_pos: Pos,
-}
-
-impl Map {
-    pub fn new(name: &str, width: i32, height: i32) -> Self {
-        let tile_matrix = Vec::new();
-        let mut map = Self {
-            name: String::from(name),
-            food: Vec::new(),
-            width,
-            height,
-            tile_matrix,
-            nest_pos: Pos::new(width / 2, height / 2),
-        };
-        map.tile_matrix = map.init_map();
-        return map;
-    }
-
-    fn init_map(&self) -> Vec<Vec<Tile>> {
-        let mut tile_matrix: Vec<Vec<Tile>> = Vec::new();
-        for y in 0..self.height {
-            let mut row: Vec<Tile> = Vec::new();
-            for x in 0..self.width {
-                row.push(Tile::empty(x, y));
-            }
-            tile_matrix.push(row);
-        }
-
-        // anthill
-        tile_matrix[self.nest_pos.y as usize][self.nest_pos.x as usize] = Tile::nest(self.nest_pos);
-        // for y in self.nest_pos.y - ANTHILL_HEIGHT / 2..self.nest_pos.y + ANTHILL_HEIGHT / 2 {
-        //     for x in self.nest_pos.x - ANTHILL_WIDTH / 2..self.nest_pos.x + ANTHILL_WIDTH / 2 {
-        //         tile_matrix[(y) as usize][(x) as usize] = Tile::nest(x, y);
-        //     }
-        // }
-
-        // random food; TEMPORARY
-        let mut food_piles = 0;
-        while food_piles < 8 {
-            let mut rng = rand::thread_rng();
-
-            let food_block_x = 20;
-            let food_block_y = (food_block_x as f32 * 0.3) as i32;
-
-            let rand_x = rng.gen_range(0..self.width - food_block_x);
-            let rand_y = rng.gen_range(0..self.height - food_block_y);
-
-            if (rand_x < self.nest_pos.x - 10 || rand_x > self.nest_pos.x + 10)
-                && (rand_y < self.nest_pos.y - 10 || rand_y > self.nest_pos.y + 10)
-            {
-                for y in rand_y..rand_y + food_block_y {
-                    for x in rand_x..rand_x + food_block_x {
-                        tile_matrix[y as usize][x as usize] = Tile::food(x, y);
-                    }
-                }
-                food_piles += 1;
-            }
-        }
-
-        return tile_matrix;
-    }
-
-    pub fn get(&self, pos: Pos) -> &Tile {
-        return &self.tile_matrix[pos.y as usize][pos.x as usize];
-    }
-
-    pub fn get_xy(&self, x: i32, y: i32) -> &Tile {
-        let pos = Pos::new(x, y);
-        return self.get(pos);
-    }
-
-    pub fn get_mut(&mut self, pos: Pos) -> &mut Tile {
-        return &mut self.tile_matrix[pos.y as usize][pos.x as usize];
-    }
-
-    pub fn clear(&mut self, pos: Pos) {
-        self.get_mut(pos).tile_type = TileType::Empty;
-    }
-
-    pub fn drop_ph(&mut self, ph_type: PhType, pos: Pos, amount: i32) {
-        if let Some(ph) = &mut self.get_mut(pos).pheromone {
-            ph.concentration += amount;
-        } else {
-            self.get_mut(pos).pheromone = Some(Pheromone::food(amount));
-        }
-    }
-
-    pub fn min_height(&self) -> i32 {
-        -self.height / 2
-    }
-
-    pub fn max_height(&self) -> i32 {
-        self.height / 2
-    }
-
-    pub fn min_width(&self) -> i32 {
-        -self.width / 2
-    }
-
-    pub fn max_width(&self) -> i32 {
-        self.width / 2
-    }
-
-    pub fn get_neightbours(&self, pos: Pos) -> Vec<Tile> {
-        let mut neighbours: Vec<Tile> = Vec::new();
-
-        for y in self.clamp_height(pos.y - 1)..=self.clamp_height(pos.y + 1) {
-            for x in self.clamp_width(pos.x - 1)..=self.clamp_width(pos.x + 1) {
-                if !((x == pos.x) && (y == pos.y)) {
-                    neighbours.push(*self.get_xy(x, y));
-                }
-            }
-        }
-
-        return neighbours;
-    }
-
-    pub fn reached_nest(&self, pos: Pos) -> bool {
-        let neighbours = self.get_neightbours(pos);
-        if neighbours.into_iter().any(|nt| nt.pos == self.nest_pos) {
-            return true;
-        } else {
-            return false;
-        }
-    }
-
-    pub fn nest_ph(&self) -> f32 {
-        todo!();
-    }
-
-    fn clamp_height(&self, num: i32) -> i32 {
-        num.clamp(0, self.height - 1)
-    }
-
-    fn clamp_width(&self, num: i32) -> i32 {
-        num.clamp(0, self.width - 1)
-    }
-}
-
-#[derive(Deserialize, Clone, Copy, Default, PartialEq, Eq)]
-pub struct Pos {
-    pub x: i32,
-    pub y: i32,
-}
-
-impl Pos {
-    pub fn new(x: i32, y: i32) -> Self {
-        Self { x, y }
-    }
-
-    pub fn dist(pos1: Pos, pos2: Pos) -> f32 {
-        let a: f32 = (pos1.x - pos2.x).pow(2) as f32;
-        let b: f32 = (pos1.y - pos2.y).pow(2) as f32;
-        let c: f32 = (a + b).sqrt();
-        return c;
-    }
-}
-
-#[derive(Deserialize, Clone, Copy)]
-pub struct Tile {
-    pub tile_type: TileType,
-    pub pheromone: Option<Pheromone>,
-    pub pos: Pos,
-}
-
-impl Tile {
-    pub fn empty(x: i32, y: i32) -> Self {
-        Self {
-            tile_type: TileType::Empty,
-            pheromone: None,
-            pos: Pos::new(x, y),
-        }
-    }
-
-    pub fn nest(pos: Pos) -> Self {
-        Self {
-            tile_type: TileType::Nest,
-            pheromone: None,
-            pos,
-        }
-    }
-
-    pub fn border(x: i32, y: i32) -> Self {
-        Self {
-            tile_type: TileType::Border,
-            pheromone: None,
-            pos: Pos::new(x, y),
-        }
-    }
-
-    pub fn food(x: i32, y: i32) -> Self {
-        Self {
-            tile_type: TileType::Food,
-            pheromone: None,
-            pos: Pos::new(x, y),
-        }
-    }
-
-    pub fn char(&self) -> char {
-        self.tile_type.char()
-    }
-
-    pub fn evaporate(&mut self, rate: i32) {
-        match &mut self.pheromone {
-            Some(ph) => {
-                let mut conc: f32 = ph.conc() as f32;
-                conc = conc * (1. - (rate as f32 / 100.));
-                if conc < 30. {
-                    self.pheromone = None;
-                } else {
-                    ph.concentration = conc as i32;
-                }
-            }
-            None => {}
-        }
-    }
-}
-
-#[derive(Deserialize, Clone, Copy, PartialEq, Eq)]
-pub enum TileType {
-    Border,
-    Empty,
-    Food,
-    Nest,
-}
-
-impl TileType {
-    pub fn char(&self) -> char {
-        match &self {
-            TileType::Border => ' ',
-            TileType::Empty => ' ',
-            TileType::Food => '@',
-            TileType::Nest => 'H',
-        }
-    }
-}
-
-#[derive(Deserialize, Clone, Copy)]
-pub struct Pheromone {
-    pub ph_type: PhType,
-    pub concentration: i32,
-}
-
-impl Pheromone {
-    pub fn expl(amount: i32) -> Self {
-        Self {
-            ph_type: PhType::Exploration,
-            concentration: amount,
-        }
-    }
-
-    pub fn food(amount: i32) -> Self {
-        Self {
-            ph_type: PhType::FoodTrail,
-            concentration: amount,
-        }
-    }
-
-    pub fn ph_type(&self) -> PhType {
-        return self.ph_type.clone();
-    }
-
-    pub fn conc(&self) -> i32 {
-        return self.concentration;
-    }
-}
-
-#[derive(Deserialize, Clone, Copy)]
-pub enum PhType {
-    Exploration,
-    FoodTrail,
-}
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fs,
+    path::Path,
+};
+
+use anyhow::{bail, Context, Result};
+use enum_index::{EnumIndex, IndexEnum};
+use enum_index_derive::{EnumIndex, IndexEnum};
+use getset::{Getters, MutGetters, Setters};
+use ordered_float::OrderedFloat;
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+
+/// Upper bound of colonies sharing a map.
+pub const MAX_COLONIES: usize = 4;
+
+/// Glyph of empty ground in text grids, so the lines keep their length.
+const ASCII_EMPTY: char = '.';
+
+use super::{
+    area::{Area, AreaType},
+    distance::DistanceField,
+    generator::{label_sources, Generator},
+};
+
+/// A map is either generated via `Map::new` (see `Generator`) or loaded from a RON file via `Map::load`.
+///
+/// Map file format (`x_scale` and `y_scale` are optional):
+/// ```ron
+/// (
+///     name: "Two patches",
+///     width: 115,
+///     height: 46,
+///     nests: [(x: 57, y: 23)], // one colony per nest
+///     x_scale: 1.0,
+///     y_scale: 1.0,
+///     areas: [
+///         (
+///             area_type: Food,
+///             amount: Some(10), // optional food per tile
+///             regrowth: Some(0.01), // optional food regrown per tile and step
+///             appear: Some(500), // optional step the food appears at
+///             vanish: Some(2000), // optional step the food disappears at
+///             borders: [
+///                 (func: "-0.05*(x-20)^2+12", direction: Beneath),
+///                 (func: "0.05*(x-20)^2+4", direction: Above),
+///             ],
+///         ),
+///         (
+///             area_type: Terrain, // only changes the ground, see `Terrain`
+///             terrain: Mud,
+///             borders: [(func: "30", direction: Above)],
+///         ),
+///     ],
+///     // optional single tiles drawn over the areas, written by the map editor
+///     tiles: [
+///         (pos: (x: 3, y: 4), tile_type: Food, food: Some(10)),
+///         (pos: (x: 3, y: 5), tile_type: Border),
+///         (pos: (x: 3, y: 6), tile_type: Empty, terrain: Sand),
+///         (pos: (x: 8, y: 2), tile_type: Food, food: Some(5), source: Some(0)),
+///     ],
+///     // optional regrowth and schedule of the food sources given by the tiles' `source`
+///     sources: [(regrowth: 0.01, appear: Some(500), vanish: None)],
+/// )
+/// ```
+#[derive(Getters, MutGetters, Setters, Serialize, Deserialize, Default)]
+pub struct Map {
+    #[getset(get = "pub")]
+    name: String,
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing)]
+    areas: Vec<Area>,
+    #[getset(get = "pub")]
+    width: i32,
+    #[getset(get = "pub")]
+    height: i32,
+    /// Scaling applied to the area functions, e.g. `2.0` draws a map designed for 50 tiles onto 100.
+    #[getset(get = "pub")]
+    #[serde(default = "default_scale")]
+    x_scale: f64,
+    #[getset(get = "pub")]
+    #[serde(default = "default_scale")]
+    y_scale: f64,
+    #[serde(skip)]
+    pub tile_matrix: Vec<Vec<Tile>>,
+    /// Nest of every colony, indexed by `Colony::id`.
+    pub nests: Vec<Pos>,
+    /// Footprint and entrances of every nest, sized by the anthill config.
+    #[serde(skip)]
+    pub colony_nests: Vec<Nest>,
+    /// Behaviour at the map edges, taken from the config.
+    #[serde(skip)]
+    pub boundary: Boundary,
+    /// Single tiles painted over the areas.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tiles: Vec<PaintedTile>,
+    /// Regrowth and schedule of the food sources of the painted tiles.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    sources: Vec<SourceSchedule>,
+    /// Food areas and random piles, indexed by `Tile::source`.
+    #[serde(skip)]
+    pub food_sources: Vec<FoodSource>,
+    /// Distance to the nest of every colony around obstacles, see `Map::compute_distances`.
+    #[serde(skip)]
+    pub nest_distances: Vec<DistanceField>,
+}
+
+impl Map {
+    pub fn new(name: &str, config: &Config) -> Self {
+        let width = config.map_width.val();
+        let height = config.map_height.val();
+        let tile_matrix = Vec::new();
+        let mut map = Self {
+            name: String::from(name),
+            areas: Vec::new(),
+            width,
+            height,
+            x_scale: 1.,
+            y_scale: 1.,
+            tile_matrix,
+            nests: Map::nest_positions(width, height, config.colonies.val()),
+            colony_nests: Vec::new(),
+            boundary: Boundary::from_config(config),
+            tiles: Vec::new(),
+            sources: Vec::new(),
+            food_sources: Vec::new(),
+            nest_distances: Vec::new(),
+        };
+        map.build_nests(config);
+        map.tile_matrix = Generator::from_config(config).generate(&map, config);
+        map.count_food();
+        for source in map.food_sources.iter_mut() {
+            source.regrowth = config.food_regrowth.val() as f32 / 1000.;
+        }
+        map.compute_distances();
+        return map;
+    }
+
+    /// Loads a RON map file, or a text grid if the file ends in `.txt` (see `Map::from_ascii`).
+    pub fn load<P: AsRef<Path>>(path: P, config: &Config) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed reading map file {}", path.display()))?;
+        let map = if is_ascii_path(path) {
+            let name = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            Map::from_ascii(&name, &contents, config)
+        } else {
+            Map::from_ron(&contents, config)
+        };
+        map.with_context(|| format!("failed loading map file {}", path.display()))
+    }
+
+    /// Reads a map drawn as a text grid with the glyphs of `TileType::char`:
+    /// `H` nest, `@` food, `X` obstacle and ` ` or `.` for empty tiles.
+    /// Empty tiles with terrain use the glyphs of `Terrain::char`.
+    /// Short lines are filled up with empty tiles. Every connected group of `H` is a nest,
+    /// numbered row by row, so nests can be drawn as rectangles of any size.
+    /// Every connected patch of food becomes its own source.
+    /// ```text
+    /// XXXXXXXXXX
+    /// X@@.....XX
+    /// X@...H...X
+    /// XXXXXXXXXX
+    /// ```
+    pub fn from_ascii(name: &str, contents: &str, config: &Config) -> Result<Self> {
+        let lines: Vec<&str> = contents.lines().collect();
+        let mut map = Self {
+            name: String::from(name),
+            width: lines.iter().map(|l| l.chars().count()).max().unwrap_or(0) as i32,
+            height: lines.len() as i32,
+            x_scale: 1.,
+            y_scale: 1.,
+            boundary: Boundary::from_config(config),
+            ..Default::default()
+        };
+        let glyphs: Vec<Vec<char>> = lines.iter().map(|l| l.chars().collect()).collect();
+        let is_nest = |x: i32, y: i32| {
+            x >= 0
+                && y >= 0
+                && glyphs.get(y as usize).and_then(|row| row.get(x as usize))
+                    == Some(&TileType::Nest.char())
+        };
+        let mut seen = HashSet::new();
+        let mut bounds = Vec::new();
+        for y in 0..map.height {
+            for x in 0..map.width {
+                if !is_nest(x, y) || !seen.insert((x, y)) {
+                    continue;
+                }
+                let (mut min, mut max) = (Pos::new(x, y), Pos::new(x, y));
+                let mut tiles = 0;
+                let mut queue = VecDeque::new();
+                queue.push_back((x, y));
+                while let Some((tx, ty)) = queue.pop_front() {
+                    tiles += 1;
+                    min = Pos::new(min.x.min(tx), min.y.min(ty));
+                    max = Pos::new(max.x.max(tx), max.y.max(ty));
+                    for (nx, ny) in [(tx - 1, ty), (tx + 1, ty), (tx, ty - 1), (tx, ty + 1)] {
+                        if is_nest(nx, ny) && seen.insert((nx, ny)) {
+                            queue.push_back((nx, ny));
+                        }
+                    }
+                }
+                // nests cover a rectangle, see `Nest`
+                if tiles != (max.x - min.x + 1) * (max.y - min.y + 1) {
+                    bail!("nest at ({}, {}) is not a rectangle", x, y);
+                }
+                bounds.push((min, max));
+            }
+        }
+        map.colony_nests = bounds
+            .into_iter()
+            .map(|(min, max)| Nest::from_bounds(min, max, config.nest_entrances.val()))
+            .collect();
+        map.nests = map.colony_nests.iter().map(|nest| nest.center).collect();
+        map.validate()?;
+
+        map.tile_matrix = map.init_tiles();
+        for (y, line) in lines.iter().enumerate() {
+            for (x, glyph) in line.chars().enumerate() {
+                let (x, y) = (x as i32, y as i32);
+                map.tile_matrix[y as usize][x as usize] = match TileType::from_char(glyph) {
+                    Some(TileType::Nest) => Tile::nest(Pos::new(x, y)),
+                    Some(TileType::Food) => Tile::food(x, y, config.food_amount.val(), 0),
+                    Some(TileType::Border) => Tile::border(x, y),
+                    Some(TileType::Empty) => Tile::empty(x, y),
+                    None if glyph == ASCII_EMPTY => Tile::empty(x, y),
+                    None => match Terrain::from_char(glyph) {
+                        Some(terrain) => Tile::empty(x, y).with_terrain(terrain),
+                        None => bail!("unknown tile '{}' at ({}, {})", glyph, x, y),
+                    },
+                };
+            }
+        }
+        label_sources(&mut map.tile_matrix, 0);
+        map.count_food();
+        for source in map.food_sources.iter_mut() {
+            source.regrowth = config.food_regrowth.val() as f32 / 1000.;
+        }
+        map.compute_distances();
+        Ok(map)
+    }
+
+    /// Draws the map as a text grid, readable by `Map::from_ascii`. Empty tiles are written as `.`
+    /// so the lines keep their length.
+    pub fn to_ascii(&self) -> String {
+        let mut contents = String::new();
+        for row in self.tile_matrix.iter() {
+            for tile in row.iter() {
+                contents.push(match (tile.tile_type, tile.terrain) {
+                    (TileType::Empty, Terrain::Ground) => ASCII_EMPTY,
+                    (TileType::Empty, terrain) => terrain.char(),
+                    _ => tile.char(),
+                });
+            }
+            contents.push('\n');
+        }
+        contents
+    }
+
+    pub fn from_ron(contents: &str, config: &Config) -> Result<Self> {
+        let mut map: Map = ron::from_str(contents).context("failed parsing map")?;
+        map.boundary = Boundary::from_config(config);
+        map.validate()?;
+        map.build_nests(config);
+        map.tile_matrix = map.init_tiles();
+        map.rasterize(config.food_amount.val());
+        map.paint_tiles(config.food_amount.val());
+        map.count_food();
+
+        let food_areas = map
+            .areas
+            .iter()
+            .filter(|a| matches!(a.area_type, AreaType::Food));
+        let area_count = food_areas.clone().count();
+        for (source, area) in map.food_sources.iter_mut().zip(food_areas) {
+            source.regrowth = area
+                .regrowth
+                .unwrap_or(config.food_regrowth.val() as f32 / 1000.);
+            source.appear = area.appear;
+            source.vanish = area.vanish;
+        }
+        let painted_sources = map.food_sources.iter_mut().skip(area_count);
+        for (source, schedule) in painted_sources.zip(map.sources.iter()) {
+            source.regrowth = schedule.regrowth;
+            source.appear = schedule.appear;
+            source.vanish = schedule.vanish;
+        }
+        map.update_food(0);
+        map.compute_distances();
+        Ok(map)
+    }
+
+    /// Writes the map as a RON file, or as a text grid if the file ends in `.txt`.
+    /// The areas are not kept, instead every obstacle and terrain tile is written to the `tiles`
+    /// list. Food tiles are written with the source they belong to and their initial amount,
+    /// also while their source is out of schedule, and the `sources` list keeps the regrowth
+    /// and schedule of every source.
+    pub fn save<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        if is_ascii_path(path) {
+            return fs::write(path, self.to_ascii())
+                .with_context(|| format!("failed writing map file {}", path.display()));
+        }
+
+        let food: HashMap<Pos, (usize, i32)> = self
+            .food_sources
+            .iter()
+            .enumerate()
+            .flat_map(|(id, source)| {
+                source
+                    .tiles
+                    .iter()
+                    .map(move |(pos, amount)| (*pos, (id, *amount)))
+            })
+            .collect();
+        self.tiles = self
+            .tile_matrix
+            .iter()
+            .flatten()
+            .filter(|t| {
+                t.tile_type == TileType::Border
+                    || t.terrain != Terrain::Ground
+                    || food.contains_key(&t.pos)
+            })
+            .map(|t| match food.get(&t.pos) {
+                Some((source, amount)) => PaintedTile {
+                    pos: t.pos,
+                    tile_type: TileType::Food,
+                    food: Some(*amount),
+                    terrain: t.terrain,
+                    source: Some(*source),
+                },
+                None => PaintedTile {
+                    pos: t.pos,
+                    tile_type: match t.tile_type {
+                        TileType::Nest | TileType::Food => TileType::Empty,
+                        tile_type => tile_type,
+                    },
+                    food: None,
+                    terrain: t.terrain,
+                    source: None,
+                },
+            })
+            .collect();
+        self.sources = self
+            .food_sources
+            .iter()
+            .map(|source| SourceSchedule {
+                regrowth: source.regrowth,
+                appear: source.appear,
+                vanish: source.vanish,
+            })
+            .collect();
+
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::new())
+            .context("failed serializing map")?;
+        self.tiles.clear();
+        self.sources.clear();
+        fs::write(path, contents)
+            .with_context(|| format!("failed writing map file {}", path.display()))
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.width <= 0 || self.height <= 0 {
+            bail!("map size {}x{} is not positive", self.width, self.height);
+        }
+        if self.nests.is_empty() || self.nests.len() > MAX_COLONIES {
+            bail!(
+                "map has {} nests, expected 1 to {}",
+                self.nests.len(),
+                MAX_COLONIES
+            );
+        }
+        for nest in self.nests.iter() {
+            if !self.in_bounds(*nest) {
+                bail!(
+                    "nest position ({}, {}) lies outside the {}x{} map",
+                    nest.x,
+                    nest.y,
+                    self.width,
+                    self.height
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Lays out the footprint of every nest around its position.
+    pub fn build_nests(&mut self, config: &Config) {
+        self.colony_nests = self
+            .nests
+            .iter()
+            .map(|center| {
+                Nest::new(
+                    *center,
+                    config.anthill_width.val(),
+                    config.anthill_height.val(),
+                    config.nest_entrances.val(),
+                    self,
+                )
+            })
+            .collect();
+    }
+
+    /// Searches the distance to every nest from scratch, after the map was built or a nest moved.
+    pub fn compute_distances(&mut self) {
+        self.nest_distances = self
+            .colony_nests
+            .iter()
+            .map(|nest| DistanceField::new(self, nest))
+            .collect();
+    }
+
+    /// Steps from the position to the nest of the colony, walking around obstacles.
+    /// `None` if the position is blocked or cut off from the nest.
+    pub fn nest_distance(&self, colony: usize, pos: Pos) -> Option<u32> {
+        self.nest_distances
+            .get(colony)
+            .and_then(|field| field.get(pos))
+    }
+
+    /// Keeps the distances up to date after the tile at `pos` turned passable or impassable.
+    /// Food appearing, vanishing or regrowing keeps tiles passable and needs no update.
+    fn update_distances(&mut self, pos: Pos) {
+        let mut fields = std::mem::take(&mut self.nest_distances);
+        for field in fields.iter_mut() {
+            field.update(self, pos);
+        }
+        self.nest_distances = fields;
+    }
+
+    /// Whether the position lies on the footprint of any nest.
+    pub fn is_nest(&self, pos: Pos) -> bool {
+        self.colony_nests.iter().any(|nest| nest.contains(pos))
+    }
+
+    /// A single nest sits in the middle of the map, several nests are spread on a circle around it.
+    fn nest_positions(width: i32, height: i32, count: i32) -> Vec<Pos> {
+        let center = Pos::new(width / 2, height / 2);
+        if count <= 1 {
+            return vec![center];
+        }
+
+        (0..count)
+            .map(|i| {
+                let angle = i as f32 / count as f32 * 2. * std::f32::consts::PI;
+                Pos::new(
+                    center.x + (angle.cos() * width as f32 / 3.) as i32,
+                    center.y + (angle.sin() * height as f32 / 3.) as i32,
+                )
+            })
+            .collect()
+    }
+
+    pub(super) fn init_tiles(&self) -> Vec<Vec<Tile>> {
+        let mut tile_matrix: Vec<Vec<Tile>> = Vec::new();
+        for y in 0..self.height {
+            let mut row: Vec<Tile> = Vec::new();
+            for x in 0..self.width {
+                row.push(Tile::empty(x, y));
+            }
+            tile_matrix.push(row);
+        }
+
+        // anthill
+        for pos in self.colony_nests.iter().flat_map(|nest| nest.tiles()) {
+            tile_matrix[pos.y as usize][pos.x as usize] = Tile::nest(pos);
+        }
+
+        return tile_matrix;
+    }
+
+    /// Paints all areas into the tile matrix. Areas are drawn in order, later areas overwrite
+    /// earlier ones. The nest positions are never overwritten, terrain areas only change the terrain.
+    /// Every food area becomes its own food source, holding `food_amount` per tile
+    /// unless the area defines an amount.
+    pub fn rasterize(&mut self, food_amount: i32) {
+        let mut source = 0;
+        for area in self.areas.iter() {
+            for row in self.tile_matrix.iter_mut() {
+                for tile in row.iter_mut() {
+                    if !self.colony_nests.iter().any(|nest| nest.contains(tile.pos))
+                        && area.inside(
+                            tile.pos.x as f64,
+                            tile.pos.y as f64,
+                            self.x_scale,
+                            self.y_scale,
+                        )
+                    {
+                        let terrain = match area.area_type {
+                            AreaType::Terrain => area.terrain,
+                            _ => tile.terrain,
+                        };
+                        *tile = match area.area_type {
+                            AreaType::Anthill => Tile::nest(tile.pos),
+                            AreaType::Food => Tile::food(
+                                tile.pos.x,
+                                tile.pos.y,
+                                area.amount.unwrap_or(food_amount),
+                                source,
+                            ),
+                            AreaType::Obstacle => Tile::border(tile.pos.x, tile.pos.y),
+                            AreaType::Terrain => *tile,
+                        }
+                        .with_terrain(terrain);
+                    }
+                }
+            }
+            if let AreaType::Food = area.area_type {
+                source += 1;
+            }
+        }
+    }
+
+    /// Paints the single tiles of a map file over the areas. Connected painted food
+    /// tiles form a food source, numbered after the food areas.
+    fn paint_tiles(&mut self, food_amount: i32) {
+        let area_sources = self
+            .areas
+            .iter()
+            .filter(|a| matches!(a.area_type, AreaType::Food))
+            .count();
+        // tiles without a source are grouped by adjacency after the listed sources
+        let first_source = area_sources
+            + self
+                .tiles
+                .iter()
+                .filter_map(|t| t.source)
+                .max()
+                .map_or(0, |s| s + 1)
+                .max(self.sources.len());
+        for painted in self.tiles.iter() {
+            if !self.in_bounds(painted.pos) || self.is_nest(painted.pos) {
+                continue;
+            }
+            let (x, y) = (painted.pos.x, painted.pos.y);
+            let source = painted.source.map_or(first_source, |s| area_sources + s);
+            self.tile_matrix[y as usize][x as usize] = match painted.tile_type {
+                TileType::Food => Tile::food(x, y, painted.food.unwrap_or(food_amount), source),
+                TileType::Border => Tile::border(x, y),
+                TileType::Empty | TileType::Nest => Tile::empty(x, y),
+            }
+            .with_terrain(painted.terrain);
+        }
+        label_sources(&mut self.tile_matrix, first_source);
+    }
+
+    /// Paints a single tile, used by the map editor. Nests are moved via `Map::move_nest`.
+    /// Painted food joins the source of an adjacent food tile or starts a new one.
+    pub fn paint(&mut self, pos: Pos, tile_type: TileType, food_amount: i32) {
+        if !self.in_bounds(pos) || self.is_nest(pos) {
+            return;
+        }
+        self.remove_food(pos);
+
+        let passable = self.passable(pos);
+        let terrain = self.get(pos).terrain;
+        *self.get_mut(pos) = match tile_type {
+            TileType::Food => {
+                let source = self
+                    .get_neightbours(pos)
+                    .iter()
+                    .filter(|t| t.tile_type == TileType::Food)
+                    .find_map(|t| t.source)
+                    .unwrap_or(self.food_sources.len());
+                if source == self.food_sources.len() {
+                    self.food_sources.push(FoodSource::new());
+                }
+                let food_source = &mut self.food_sources[source];
+                food_source.initial += food_amount;
+                food_source.tiles.push((pos, food_amount));
+                Tile::food(pos.x, pos.y, food_amount, source)
+            }
+            TileType::Border => Tile::border(pos.x, pos.y),
+            TileType::Empty | TileType::Nest => Tile::empty(pos.x, pos.y),
+        }
+        .with_terrain(terrain);
+        if let Some(source) = self.get(pos).source {
+            self.recount_source(source);
+        }
+        if self.passable(pos) != passable {
+            self.update_distances(pos);
+        }
+    }
+
+    /// Sets the terrain of a single tile, used by the map editor.
+    pub fn set_terrain(&mut self, pos: Pos, terrain: Terrain) {
+        if self.in_bounds(pos) {
+            self.get_mut(pos).terrain = terrain;
+        }
+    }
+
+    /// Moves the nest of a colony, keeping its size. The old footprint is cleared.
+    /// Nothing happens if the nest would overlap another nest.
+    pub fn move_nest(&mut self, colony: usize, pos: Pos) {
+        if !self.in_bounds(pos) || colony >= self.colony_nests.len() {
+            return;
+        }
+        let old = self.colony_nests[colony].clone();
+        let nest = Nest::new(
+            pos,
+            old.max.x - old.min.x + 1,
+            old.max.y - old.min.y + 1,
+            old.entrances.len() as i32,
+            self,
+        );
+        let overlaps = self
+            .colony_nests
+            .iter()
+            .enumerate()
+            .any(|(id, other)| id != colony && nest.tiles().any(|p| other.contains(p)));
+        if overlaps {
+            return;
+        }
+
+        for tile in old.tiles() {
+            let terrain = self.get(tile).terrain;
+            *self.get_mut(tile) = Tile::empty(tile.x, tile.y).with_terrain(terrain);
+        }
+        for tile in nest.tiles() {
+            self.remove_food(tile);
+            let terrain = self.get(tile).terrain;
+            *self.get_mut(tile) = Tile::nest(tile).with_terrain(terrain);
+        }
+        self.nests[colony] = nest.center;
+        self.colony_nests[colony] = nest;
+        self.compute_distances();
+    }
+
+    /// Removes a tile from its food source.
+    fn remove_food(&mut self, pos: Pos) {
+        if let Some(id) = self.get(pos).source {
+            let source = &mut self.food_sources[id];
+            if let Some(index) = source.tiles.iter().position(|(p, _)| *p == pos) {
+                let (_, amount) = source.tiles.remove(index);
+                source.initial -= amount;
+            }
+            let terrain = self.get(pos).terrain;
+            *self.get_mut(pos) = Tile::empty(pos.x, pos.y).with_terrain(terrain);
+            self.recount_source(id);
+        }
+    }
+
+    /// Collects the food sources from the tile matrix.
+    pub fn count_food(&mut self) {
+        let mut sources: Vec<FoodSource> = Vec::new();
+        for tile in self.tile_matrix.iter().flatten() {
+            if let (TileType::Food, Some(source)) = (tile.tile_type, tile.source) {
+                if sources.len() <= source {
+                    sources.resize(source + 1, FoodSource::new());
+                }
+                sources[source].initial += tile.food;
+                sources[source].remaining += tile.food;
+                sources[source].tiles.push((tile.pos, tile.food));
+            }
+        }
+        self.food_sources = sources;
+    }
+
+    /// Regrows food and lets scheduled sources appear or vanish.
+    pub fn update_food(&mut self, step: i32) {
+        for id in 0..self.food_sources.len() {
+            let source = &self.food_sources[id];
+            let scheduled = source.appear.map_or(true, |s| step >= s)
+                && source.vanish.map_or(true, |s| step < s);
+
+            if scheduled != source.active {
+                self.set_source_active(id, scheduled);
+            } else if source.active && source.regrowth > 0. {
+                self.regrow(id);
+            }
+        }
+    }
+
+    fn set_source_active(&mut self, id: usize, active: bool) {
+        let tiles = self.food_sources[id].tiles.clone();
+        for (pos, amount) in tiles {
+            let tile = self.get_mut(pos);
+            if active && tile.tile_type == TileType::Empty {
+                tile.tile_type = TileType::Food;
+                tile.food = amount;
+                tile.source = Some(id);
+            } else if !active && tile.source == Some(id) {
+                tile.tile_type = TileType::Empty;
+                tile.food = 0;
+                tile.source = None;
+            }
+        }
+        self.food_sources[id].active = active;
+        self.recount_source(id);
+    }
+
+    fn regrow(&mut self, id: usize) {
+        let source = &mut self.food_sources[id];
+        source.growth += source.regrowth;
+        if source.growth < 1. {
+            return;
+        }
+        let amount = source.growth as i32;
+        source.growth -= amount as f32;
+
+        let tiles = source.tiles.clone();
+        for (pos, capacity) in tiles {
+            let tile = self.get_mut(pos);
+            if tile.tile_type == TileType::Food && tile.source == Some(id) {
+                tile.food = (tile.food + amount).min(capacity);
+            } else if tile.tile_type == TileType::Empty {
+                tile.tile_type = TileType::Food;
+                tile.food = amount.min(capacity);
+                tile.source = Some(id);
+            }
+        }
+        self.recount_source(id);
+    }
+
+    fn recount_source(&mut self, id: usize) {
+        let mut remaining = 0;
+        for (pos, _) in self.food_sources[id].tiles.iter() {
+            let tile = self.get(*pos);
+            if tile.tile_type == TileType::Food && tile.source == Some(id) {
+                remaining += tile.food;
+            }
+        }
+        self.food_sources[id].remaining = remaining;
+    }
+
+    pub fn in_bounds(&self, pos: Pos) -> bool {
+        pos.x >= 0 && pos.x < self.width && pos.y >= 0 && pos.y < self.height
+    }
+
+    /// Whether an ant may enter the tile at `pos`.
+    pub fn passable(&self, pos: Pos) -> bool {
+        self.in_bounds(pos) && self.get(pos).passable()
+    }
+
+    /// Whether an ant can step from `from` onto the neighbouring tile `to`. A diagonal step may
+    /// not squeeze between two obstacles touching at their corners.
+    pub fn can_step(&self, from: Pos, to: Pos) -> bool {
+        let (dx, dy) = self.delta(from, to);
+        self.passable(to)
+            && (dx == 0
+                || dy == 0
+                || self.passable(self.bound(Pos::new(from.x + dx, from.y)))
+                || self.passable(self.bound(Pos::new(from.x, from.y + dy))))
+    }
+
+    pub fn get(&self, pos: Pos) -> &Tile {
+        return &self.tile_matrix[pos.y as usize][pos.x as usize];
+    }
+
+    pub fn get_xy(&self, x: i32, y: i32) -> &Tile {
+        let pos = Pos::new(x, y);
+        return self.get(pos);
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> &mut Tile {
+        return &mut self.tile_matrix[pos.y as usize][pos.x as usize];
+    }
+
+    pub fn clear(&mut self, pos: Pos) {
+        self.get_mut(pos).tile_type = TileType::Empty;
+    }
+
+    /// Takes a single piece of food from the tile. The tile is cleared once its food is used up.
+    /// Returns false if there was no food to take.
+    pub fn take_food(&mut self, pos: Pos) -> bool {
+        let tile = self.get_mut(pos);
+        if tile.tile_type != TileType::Food || tile.food <= 0 {
+            return false;
+        }
+
+        tile.food -= 1;
+        let source = tile.source;
+        if tile.food == 0 {
+            // the pheromones on the tile stay
+            tile.tile_type = TileType::Empty;
+            tile.source = None;
+        }
+        if let Some(source) = source.and_then(|s| self.food_sources.get_mut(s)) {
+            source.remaining -= 1;
+        }
+        true
+    }
+
+    /// Adds pheromone to the tile, the concentration saturates at `cap`.
+    pub fn drop_ph(&mut self, colony: usize, ph_type: PhType, pos: Pos, amount: f32, cap: f32) {
+        let pheromone = &mut self.get_mut(pos).pheromone[colony][ph_type as usize];
+        if let Some(ph) = pheromone {
+            ph.concentration = (ph.concentration + amount).min(cap);
+        } else {
+            let amount = amount.min(cap);
+            *pheromone = Some(match ph_type {
+                PhType::Exploration => Pheromone::expl(amount),
+                PhType::FoodTrail => Pheromone::food(amount),
+            });
+        }
+    }
+
+    pub fn min_height(&self) -> i32 {
+        -self.height / 2
+    }
+
+    pub fn max_height(&self) -> i32 {
+        self.height / 2
+    }
+
+    pub fn min_width(&self) -> i32 {
+        -self.width / 2
+    }
+
+    pub fn max_width(&self) -> i32 {
+        self.width / 2
+    }
+
+    /// Spreads `rate` percent of every pheromone evenly onto the passable 8-neighbourhood.
+    /// All tiles spread from the same snapshot, so the result does not depend on the order of the tiles.
+    pub fn diffuse(&mut self, rate: i32) {
+        if rate <= 0 {
+            return;
+        }
+        let mut buffer: Vec<Vec<[[f32; PH_TYPES]; MAX_COLONIES]>> = self
+            .tile_matrix
+            .iter()
+            .map(|row| row.iter().map(|tile| tile.concentrations()).collect())
+            .collect();
+
+        for tile in self.tile_matrix.iter().flatten().filter(|t| t.has_ph()) {
+            let receivers: Vec<Pos> = self
+                .get_neightbours(tile.pos)
+                .iter()
+                .filter(|t| t.passable())
+                .map(|t| t.pos)
+                .collect();
+            for (colony, pheromones) in tile.pheromone.iter().enumerate() {
+                for (ph_type, ph) in pheromones.iter().enumerate() {
+                    if let Some(ph) = ph {
+                        let share = ph.conc() * rate as f32 / 100. / 8.;
+                        for pos in receivers.iter() {
+                            buffer[pos.y as usize][pos.x as usize][colony][ph_type] += share;
+                        }
+                        buffer[tile.pos.y as usize][tile.pos.x as usize][colony][ph_type] -=
+                            share * receivers.len() as f32;
+                    }
+                }
+            }
+        }
+
+        for (row, concs) in self.tile_matrix.iter_mut().zip(buffer) {
+            for (tile, conc) in row.iter_mut().zip(concs) {
+                tile.set_concentrations(conc);
+            }
+        }
+    }
+
+    pub fn get_neightbours(&self, pos: Pos) -> Vec<Tile> {
+        let mut neighbours: Vec<Tile> = Vec::new();
+
+        if self.boundary == Boundary::Wrap {
+            for y in pos.y - 1..=pos.y + 1 {
+                for x in pos.x - 1..=pos.x + 1 {
+                    let neighbour = self.bound(Pos::new(x, y));
+                    if neighbour != pos && !neighbours.iter().any(|t| t.pos == neighbour) {
+                        neighbours.push(*self.get(neighbour));
+                    }
+                }
+            }
+            return neighbours;
+        }
+
+        for y in self.clamp_height(pos.y - 1)..=self.clamp_height(pos.y + 1) {
+            for x in self.clamp_width(pos.x - 1)..=self.clamp_width(pos.x + 1) {
+                if !((x == pos.x) && (y == pos.y)) {
+                    neighbours.push(*self.get_xy(x, y));
+                }
+            }
+        }
+
+        return neighbours;
+    }
+
+    /// Brings a position back onto the map according to the boundary mode.
+    pub fn bound(&self, pos: Pos) -> Pos {
+        match self.boundary {
+            Boundary::Clamp => Pos::new(self.clamp_width(pos.x), self.clamp_height(pos.y)),
+            Boundary::Wrap => Pos::new(pos.x.rem_euclid(self.width), pos.y.rem_euclid(self.height)),
+            Boundary::Reflect => Pos::new(
+                self.clamp_width(reflect(pos.x, self.width)),
+                self.clamp_height(reflect(pos.y, self.height)),
+            ),
+        }
+    }
+
+    /// Offset from `from` to `to`, taking the shorter way around a wrapping map.
+    pub fn delta(&self, from: Pos, to: Pos) -> (i32, i32) {
+        let (mut dx, mut dy) = (to.x - from.x, to.y - from.y);
+        if self.boundary == Boundary::Wrap {
+            if dx.abs() * 2 > self.width {
+                dx -= dx.signum() * self.width;
+            }
+            if dy.abs() * 2 > self.height {
+                dy -= dy.signum() * self.height;
+            }
+        }
+        (dx, dy)
+    }
+
+    /// Distance between two positions, see `Map::delta`.
+    pub fn dist(&self, pos1: Pos, pos2: Pos) -> f32 {
+        let (dx, dy) = self.delta(pos1, pos2);
+        ((dx * dx + dy * dy) as f32).sqrt()
+    }
+
+    /// Whether the position touches the nest of the colony anywhere on its perimeter.
+    pub fn reached_nest(&self, pos: Pos, colony: usize) -> bool {
+        self.colony_nests
+            .get(colony)
+            .map_or(false, |nest| nest.touches(pos))
+    }
+
+    /// Distance to the closest nest of any colony.
+    pub fn nest_dist(&self, pos: Pos) -> f32 {
+        self.nests
+            .iter()
+            .map(|nest| self.dist(pos, *nest))
+            .fold(f32::INFINITY, f32::min)
+    }
+
+    /// Food trail pheromone of the colony around the entrances of its nest, see `PhMetric`.
+    /// Every tile outside the nest within `radius` tiles of an entrance counts once,
+    /// tiles without pheromone count as zero.
+    pub fn nest_ph(&self, colony: usize, metric: PhMetric, radius: i32) -> f32 {
+        let nest = match self.colony_nests.get(colony) {
+            Some(nest) => nest,
+            None => return 0.,
+        };
+        let mut tiles: Vec<Pos> = Vec::new();
+        for entrance in nest.entrances.iter() {
+            for y in entrance.y - radius..=entrance.y + radius {
+                for x in entrance.x - radius..=entrance.x + radius {
+                    let mut pos = Pos::new(x, y);
+                    if self.boundary == Boundary::Wrap {
+                        pos = self.bound(pos);
+                    }
+                    if self.in_bounds(pos) && !nest.contains(pos) && !tiles.contains(&pos) {
+                        tiles.push(pos);
+                    }
+                }
+            }
+        }
+
+        let concs = tiles.iter().map(|pos| {
+            self.get(*pos)
+                .ph(colony, PhType::FoodTrail)
+                .map_or(0., |ph| ph.conc())
+        });
+        match metric {
+            PhMetric::Sum => concs.sum(),
+            PhMetric::Mean => concs.sum::<f32>() / tiles.len().max(1) as f32,
+            PhMetric::Max => concs.fold(0., f32::max),
+        }
+    }
+
+    fn clamp_height(&self, num: i32) -> i32 {
+        num.clamp(0, self.height - 1)
+    }
+
+    fn clamp_width(&self, num: i32) -> i32 {
+        num.clamp(0, self.width - 1)
+    }
+}
+
+/// Footprint of a colony's nest, every tile from `min` to `max` is a nest tile.
+/// Ants leave the nest through its entrances.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Nest {
+    pub center: Pos,
+    pub min: Pos,
+    pub max: Pos,
+    /// Nest tiles in the middle of the bottom, top, left and right side, in that order.
+    pub entrances: Vec<Pos>,
+}
+
+impl Nest {
+    /// A `width` x `height` nest around `center`, cut off at the map edges.
+    pub fn new(center: Pos, width: i32, height: i32, entrances: i32, map: &Map) -> Self {
+        let min = Pos::new(
+            (center.x - (width - 1) / 2).max(0),
+            (center.y - (height - 1) / 2).max(0),
+        );
+        let max = Pos::new(
+            (center.x + width / 2).min(map.width - 1),
+            (center.y + height / 2).min(map.height - 1),
+        );
+        let mut nest = Nest::from_bounds(min, max, entrances);
+        nest.center = center;
+        nest
+    }
+
+    pub fn from_bounds(min: Pos, max: Pos, entrances: i32) -> Self {
+        let center = Pos::new((min.x + max.x) / 2, (min.y + max.y) / 2);
+        let mut nest = Self {
+            center,
+            min,
+            max,
+            entrances: Vec::new(),
+        };
+        let sides = [
+            Pos::new(center.x, max.y),
+            Pos::new(center.x, min.y),
+            Pos::new(min.x, center.y),
+            Pos::new(max.x, center.y),
+        ];
+        for entrance in sides.iter().take(entrances.max(1) as usize) {
+            if !nest.entrances.contains(entrance) {
+                nest.entrances.push(*entrance);
+            }
+        }
+        nest
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.x >= self.min.x && pos.x <= self.max.x && pos.y >= self.min.y && pos.y <= self.max.y
+    }
+
+    /// Whether the position lies on or right next to the nest.
+    pub fn touches(&self, pos: Pos) -> bool {
+        pos.x >= self.min.x - 1
+            && pos.x <= self.max.x + 1
+            && pos.y >= self.min.y - 1
+            && pos.y <= self.max.y + 1
+    }
+
+    pub fn tiles(&self) -> impl Iterator<Item = Pos> + '_ {
+        (self.min.y..=self.max.y)
+            .flat_map(move |y| (self.min.x..=self.max.x).map(move |x| Pos::new(x, y)))
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct FoodSource {
+    pub initial: i32,
+    pub remaining: i32,
+    /// Tiles of the source with their initial amount of food.
+    pub tiles: Vec<(Pos, i32)>,
+    /// Food regrown per tile and step, fractions add up over several steps.
+    pub regrowth: f32,
+    pub appear: Option<i32>,
+    pub vanish: Option<i32>,
+    pub active: bool,
+    growth: f32,
+}
+
+impl FoodSource {
+    pub fn new() -> Self {
+        Self {
+            active: true,
+            ..Default::default()
+        }
+    }
+}
+
+fn default_scale() -> f64 {
+    1.
+}
+
+/// Mirrors a coordinate that left `0..size` back onto it.
+fn reflect(num: i32, size: i32) -> i32 {
+    if num < 0 {
+        -num
+    } else if num >= size {
+        2 * (size - 1) - num
+    } else {
+        num
+    }
+}
+
+/// Behaviour at the map edges, selected via `Config::boundary`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, EnumIndex, IndexEnum)]
+pub enum Boundary {
+    /// Ants stop at the edge.
+    #[default]
+    Clamp,
+    /// Opposite edges are connected, the map is a torus.
+    Wrap,
+    /// Ants bounce off the edge.
+    Reflect,
+}
+
+impl Boundary {
+    pub fn from_config(config: &Config) -> Self {
+        Boundary::index_enum(config.boundary.val() as usize).unwrap_or(Boundary::Clamp)
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Boundary::Clamp => "clamped",
+            Boundary::Wrap => "wrap-around",
+            Boundary::Reflect => "reflecting",
+        }
+    }
+}
+
+/// How pheromones evaporate, selected via `Config::evaporation_model`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Evaporation {
+    /// The given percentage of the concentration evaporates each step.
+    Exponential(f32),
+    /// The given amount evaporates each step.
+    Linear(f32),
+    /// The concentration halves within the given number of steps.
+    HalfLife(f32),
+}
+
+impl Evaporation {
+    pub fn from_config(config: &Config) -> Self {
+        match config.evaporation_model.val() {
+            1 => Evaporation::Linear(config.evaporation_decay.val() as f32),
+            2 => Evaporation::HalfLife(config.half_life.val() as f32),
+            _ => Evaporation::Exponential(config.evaporation_rate.val() as f32),
+        }
+    }
+
+    /// Concentration after one step, `speed` scales how fast it evaporates.
+    pub fn apply(&self, conc: f32, speed: f32) -> f32 {
+        match *self {
+            Evaporation::Exponential(rate) => conc * (1. - rate * speed / 100.).max(0.),
+            Evaporation::Linear(amount) => (conc - amount * speed).max(0.),
+            Evaporation::HalfLife(steps) => conc * 0.5f32.powf(speed / steps.max(1.)),
+        }
+    }
+}
+
+/// How the pheromone around a nest is summed up, selected via `Config::nest_ph_metric`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIndex, IndexEnum)]
+pub enum PhMetric {
+    Sum,
+    Mean,
+    Max,
+}
+
+impl PhMetric {
+    pub fn from_config(config: &Config) -> Self {
+        PhMetric::index_enum(config.nest_ph_metric.val() as usize).unwrap_or(PhMetric::Sum)
+    }
+}
+
+fn is_ascii_path(path: &Path) -> bool {
+    path.extension().map_or(false, |ext| ext == "txt")
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Pos {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Pos {
+    pub fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct Tile {
+    pub tile_type: TileType,
+    /// Pheromone of every colony and type, indexed by `Colony::id` and `PhType`.
+    pub pheromone: [[Option<Pheromone>; PH_TYPES]; MAX_COLONIES],
+    pub pos: Pos,
+    /// Food left on the tile, only used by food tiles.
+    #[serde(default)]
+    pub food: i32,
+    /// Index into `Map::food_sources`.
+    #[serde(default)]
+    pub source: Option<usize>,
+    #[serde(default)]
+    pub terrain: Terrain,
+}
+
+impl Tile {
+    pub fn empty(x: i32, y: i32) -> Self {
+        Self {
+            tile_type: TileType::Empty,
+            pheromone: [[None; PH_TYPES]; MAX_COLONIES],
+            pos: Pos::new(x, y),
+            food: 0,
+            source: None,
+            terrain: Terrain::Ground,
+        }
+    }
+
+    pub fn nest(pos: Pos) -> Self {
+        Self {
+            tile_type: TileType::Nest,
+            pheromone: [[None; PH_TYPES]; MAX_COLONIES],
+            pos,
+            food: 0,
+            source: None,
+            terrain: Terrain::Ground,
+        }
+    }
+
+    pub fn border(x: i32, y: i32) -> Self {
+        Self {
+            tile_type: TileType::Border,
+            pheromone: [[None; PH_TYPES]; MAX_COLONIES],
+            pos: Pos::new(x, y),
+            food: 0,
+            source: None,
+            terrain: Terrain::Ground,
+        }
+    }
+
+    pub fn food(x: i32, y: i32, amount: i32, source: usize) -> Self {
+        Self {
+            tile_type: TileType::Food,
+            pheromone: [[None; PH_TYPES]; MAX_COLONIES],
+            pos: Pos::new(x, y),
+            food: amount,
+            source: Some(source),
+            terrain: Terrain::Ground,
+        }
+    }
+
+    pub fn with_terrain(self, terrain: Terrain) -> Self {
+        Self { terrain, ..self }
+    }
+
+    pub fn char(&self) -> char {
+        self.tile_type.char()
+    }
+
+    pub fn passable(&self) -> bool {
+        self.tile_type != TileType::Border
+    }
+
+    /// Pheromone of the given colony and type.
+    pub fn ph(&self, colony: usize, ph_type: PhType) -> Option<&Pheromone> {
+        self.pheromone[colony][ph_type as usize].as_ref()
+    }
+
+    /// Whether any pheromone lies on the tile.
+    pub fn has_ph(&self) -> bool {
+        self.pheromone.iter().flatten().any(|ph| ph.is_some())
+    }
+
+    /// Highest concentration over all colonies and types.
+    pub fn max_ph(&self) -> Option<f32> {
+        self.pheromone
+            .iter()
+            .flatten()
+            .flatten()
+            .map(|ph| OrderedFloat(ph.conc()))
+            .max()
+            .map(|conc| conc.0)
+    }
+
+    /// Concentration of every colony and type, zero without pheromone.
+    pub fn concentrations(&self) -> [[f32; PH_TYPES]; MAX_COLONIES] {
+        let mut concentrations = [[0.; PH_TYPES]; MAX_COLONIES];
+        for (conc, ph) in concentrations
+            .iter_mut()
+            .flatten()
+            .zip(self.pheromone.iter().flatten())
+        {
+            *conc = ph.map_or(0., |ph| ph.conc());
+        }
+        concentrations
+    }
+
+    pub fn set_concentrations(&mut self, concentrations: [[f32; PH_TYPES]; MAX_COLONIES]) {
+        for (pheromones, concentrations) in self.pheromone.iter_mut().zip(concentrations.iter()) {
+            for (ph_type, (ph, conc)) in pheromones.iter_mut().zip(concentrations).enumerate() {
+                *ph = match PhType::index_enum(ph_type) {
+                    Some(ph_type) if *conc > 0. => Some(Pheromone {
+                        ph_type,
+                        concentration: *conc,
+                    }),
+                    _ => None,
+                };
+            }
+        }
+    }
+
+    /// Evaporates the pheromones for one step, faster or slower depending on the terrain.
+    /// Pheromone below the `threshold` or `MIN_PH` is removed.
+    pub fn evaporate(&mut self, evaporation: Evaporation, threshold: f32) {
+        let speed = self.terrain.evaporation();
+        for pheromone in self.pheromone.iter_mut().flatten() {
+            match pheromone {
+                Some(ph) => {
+                    let conc = evaporation.apply(ph.conc(), speed);
+                    if conc < threshold.max(MIN_PH) {
+                        *pheromone = None;
+                    } else {
+                        ph.concentration = conc;
+                    }
+                }
+                None => {}
+            }
+        }
+    }
+}
+
+/// A single tile of a map file.
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct PaintedTile {
+    pub pos: Pos,
+    pub tile_type: TileType,
+    /// Food on a food tile, the config value is used if not set.
+    #[serde(default)]
+    pub food: Option<i32>,
+    #[serde(default)]
+    pub terrain: Terrain,
+    /// Food source of a food tile, see `Map::sources`. Tiles without are grouped by adjacency.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<usize>,
+}
+
+/// Regrowth and schedule of a food source made of painted tiles.
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct SourceSchedule {
+    pub regrowth: f32,
+    #[serde(default)]
+    pub appear: Option<i32>,
+    #[serde(default)]
+    pub vanish: Option<i32>,
+}
+
+/// Ground the tile is made of. Ants need more steps to leave slow terrain
+/// and pheromones evaporate faster or slower depending on the terrain.
+#[derive(
+    Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, EnumIndex, IndexEnum,
+)]
+pub enum Terrain {
+    #[default]
+    Ground,
+    Grass,
+    Sand,
+    Mud,
+    Water,
+}
+
+impl Terrain {
+    /// Movement points an ant needs to leave the tile, ants gain their `speed` each step.
+    pub fn cost(&self) -> i32 {
+        match self {
+            Terrain::Ground | Terrain::Grass => 1,
+            Terrain::Sand => 2,
+            Terrain::Mud => 3,
+            Terrain::Water => 5,
+        }
+    }
+
+    /// Factor applied to the evaporation rate.
+    pub fn evaporation(&self) -> f32 {
+        match self {
+            Terrain::Ground => 1.,
+            Terrain::Grass => 0.5,
+            Terrain::Sand => 1.5,
+            Terrain::Mud => 0.75,
+            Terrain::Water => 3.,
+        }
+    }
+
+    /// Faint glyph drawn on empty tiles without pheromones.
+    pub fn char(&self) -> char {
+        match self {
+            Terrain::Ground => ' ',
+            Terrain::Grass => '"',
+            Terrain::Sand => ',',
+            Terrain::Mud => ';',
+            Terrain::Water => 'w',
+        }
+    }
+
+    pub fn from_char(glyph: char) -> Option<Terrain> {
+        (0..5)
+            .filter_map(Terrain::index_enum)
+            .find(|t| *t != Terrain::Ground && t.char() == glyph)
+    }
+
+    /// The next terrain, used by the map editor to cycle through them.
+    pub fn next(&self) -> Terrain {
+        Terrain::index_enum((self.enum_index() + 1) % 5).unwrap_or(Terrain::Ground)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum TileType {
+    Border,
+    Empty,
+    Food,
+    Nest,
+}
+
+impl TileType {
+    pub fn from_char(glyph: char) -> Option<TileType> {
+        [
+            TileType::Border,
+            TileType::Empty,
+            TileType::Food,
+            TileType::Nest,
+        ]
+        .iter()
+        .cloned()
+        .find(|t| t.char() == glyph)
+    }
+
+    pub fn char(&self) -> char {
+        match &self {
+            TileType::Border => 'X',
+            TileType::Empty => ' ',
+            TileType::Food => '@',
+            TileType::Nest => 'H',
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct Pheromone {
+    pub ph_type: PhType,
+    pub concentration: f32,
+}
+
+impl Pheromone {
+    pub fn expl(amount: f32) -> Self {
+        Self {
+            ph_type: PhType::Exploration,
+            concentration: amount,
+        }
+    }
+
+    pub fn food(amount: f32) -> Self {
+        Self {
+            ph_type: PhType::FoodTrail,
+            concentration: amount,
+        }
+    }
+
+    pub fn ph_type(&self) -> PhType {
+        return self.ph_type.clone();
+    }
+
+    pub fn conc(&self) -> f32 {
+        return self.concentration;
+    }
+}
+
+/// Number of pheromone types, see `PhType`.
+pub const PH_TYPES: usize = 2;
+
+/// Lowest concentration kept on a tile even without a threshold, so diffusion does not leave
+/// traces of a trail all over the map.
+pub const MIN_PH: f32 = 0.1;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, EnumIndex, IndexEnum)]
+pub enum PhType {
+    /// Laid by seekers while exploring, leads back home.
+    Exploration,
+    /// Laid by returners carrying food, leads to the food.
+    FoodTrail,
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Boundary, Evaporation, Map, PhMetric, PhType, Pos, Terrain, TileType};
+    use crate::config::Config;
+
+    const MAP: &str = r#"(
+        name: "Test",
+        width: 30,
+        height: 10,
+        nests: [(x: 25, y: 5)],
+        areas: [
+            (
+                area_type: Food,
+                borders: [
+                    (func: "8", direction: Beneath),
+                    (func: "2", direction: Above),
+                ],
+            ),
+        ],
+    )"#;
+
+    #[test]
+    fn load_map() {
+        let map = Map::from_ron(MAP, &Config::default()).unwrap();
+        assert_eq!(map.name(), "Test");
+        assert_eq!(map.tile_matrix.len(), 10);
+        assert_eq!(map.tile_matrix[0].len(), 30);
+        assert!(map.get(Pos::new(25, 5)).tile_type == TileType::Nest);
+        assert!(map.get(Pos::new(3, 5)).tile_type == TileType::Food);
+        assert!(map.get(Pos::new(3, 9)).tile_type == TileType::Empty);
+    }
+
+    #[test]
+    fn load_bundled_map() {
+        let map = Map::load("assets/map.ron", &Config::default()).unwrap();
+        assert!(map
+            .tile_matrix
+            .iter()
+            .flatten()
+            .any(|t| t.tile_type == TileType::Food));
+    }
+
+    #[test]
+    fn rasterize_scaled_areas() {
+        // obstacle wall left of x = 5 and a food patch right of x = 10, both in unscaled coordinates
+        let map = MAP
+            .replace("nests: [(x: 25, y: 5)],", "nests: [(x: 15, y: 5)], x_scale: 2.0,")
+            .replace(
+                "(func: \"8\", direction: Beneath),",
+                "(func: \"8\", direction: Beneath), (func: \"10*(x-10)\", direction: Beneath),",
+            )
+            .replace(
+                "areas: [",
+                "areas: [(area_type: Obstacle, borders: [(func: \"-10*(x-5)\", direction: Beneath)]),",
+            );
+        let map = Map::from_ron(&map, &Config::default()).unwrap();
+
+        assert!(map.get(Pos::new(9, 5)).tile_type == TileType::Border);
+        assert!(map.get(Pos::new(11, 5)).tile_type == TileType::Empty);
+        assert!(map.get(Pos::new(15, 5)).tile_type == TileType::Nest);
+        assert!(map.get(Pos::new(19, 5)).tile_type == TileType::Empty);
+        assert!(map.get(Pos::new(21, 5)).tile_type == TileType::Food);
+    }
+
+    #[test]
+    fn harvest_food() {
+        let mut map = Map::from_ron(
+            &MAP.replace("area_type: Food,", "area_type: Food, amount: Some(2),"),
+            &Config::default(),
+        )
+        .unwrap();
+        let pos = Pos::new(3, 5);
+        let total = map.food_sources[0].initial;
+        assert_eq!(map.food_sources.len(), 1);
+        // 5x3 nest inside the food area
+        assert_eq!(total, 2 * (30 * 7 - 15));
+
+        assert!(map.take_food(pos));
+        assert!(map.get(pos).tile_type == TileType::Food);
+        map.drop_ph(0, PhType::FoodTrail, pos, 10., 100.);
+        assert!(map.take_food(pos));
+        assert!(map.get(pos).tile_type == TileType::Empty);
+        assert!(map.get(pos).ph(0, PhType::FoodTrail).is_some());
+        assert!(!map.take_food(pos));
+        assert_eq!(map.food_sources[0].remaining, total - 2);
+    }
+
+    #[test]
+    fn regrow_and_schedule_food() {
+        let map = MAP.replace(
+            "area_type: Food,",
+            "area_type: Food, amount: Some(2), regrowth: Some(0.5), appear: Some(10), vanish: Some(20),",
+        );
+        let mut map = Map::from_ron(&map, &Config::default()).unwrap();
+        let pos = Pos::new(3, 5);
+        assert!(map.get(pos).tile_type == TileType::Empty);
+        assert_eq!(map.food_sources[0].remaining, 0);
+
+        map.update_food(10);
+        assert!(map.get(pos).tile_type == TileType::Food);
+        assert_eq!(map.food_sources[0].remaining, map.food_sources[0].initial);
+
+        map.take_food(pos);
+        map.take_food(pos);
+        assert!(map.get(pos).tile_type == TileType::Empty);
+        map.update_food(11);
+        map.update_food(12);
+        assert!(map.get(pos).tile_type == TileType::Food);
+        assert_eq!(map.get(pos).food, 1);
+
+        map.update_food(20);
+        assert!(map.get(pos).tile_type == TileType::Empty);
+        assert_eq!(map.food_sources[0].remaining, 0);
+    }
+
+    #[test]
+    fn paint_and_save() {
+        let mut map = Map::from_ron(MAP, &Config::default()).unwrap();
+        map.paint(Pos::new(3, 1), TileType::Food, 4);
+        map.paint(Pos::new(3, 0), TileType::Food, 4);
+        map.paint(Pos::new(5, 5), TileType::Border, 0);
+        map.paint(Pos::new(25, 5), TileType::Border, 0);
+        map.move_nest(0, Pos::new(20, 5));
+        assert_eq!(map.food_sources.len(), 1);
+        // the moved nest covers 5x3 more food tiles
+        assert_eq!(
+            map.food_sources[0].initial,
+            5 * (30 * 7 - 15) + 2 * 4 - 5 - 15 * 5
+        );
+        assert!(map.get(Pos::new(25, 5)).tile_type == TileType::Empty);
+        assert!(map.get(Pos::new(22, 6)).tile_type == TileType::Nest);
+
+        let path = std::env::temp_dir().join("ant_sim_paint_and_save.ron");
+        map.save(&path).unwrap();
+        let loaded = Map::load(&path, &Config::default()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(loaded.nests == vec![Pos::new(20, 5)]);
+        assert!(loaded.get(Pos::new(5, 5)).tile_type == TileType::Border);
+        assert!(loaded.get(Pos::new(3, 0)).tile_type == TileType::Food);
+        assert_eq!(loaded.food_sources.len(), 1);
+        assert_eq!(loaded.food_sources[0].initial, map.food_sources[0].initial);
+    }
+
+    #[test]
+    fn save_food_schedule() {
+        let mut map = Map::from_ron(MAP, &Config::default()).unwrap();
+        map.paint(Pos::new(10, 0), TileType::Food, 4);
+        assert_eq!(map.food_sources.len(), 2);
+        map.food_sources[0].regrowth = 0.5;
+        map.food_sources[0].vanish = Some(80);
+        map.food_sources[1].appear = Some(40);
+        // the second source is not on the map yet
+        map.update_food(0);
+        assert!(map.get(Pos::new(10, 0)).tile_type == TileType::Empty);
+
+        let path = std::env::temp_dir().join("ant_sim_save_food_schedule.ron");
+        map.save(&path).unwrap();
+        let loaded = Map::load(&path, &Config::default()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.food_sources.len(), 2);
+        for (loaded, source) in loaded.food_sources.iter().zip(map.food_sources.iter()) {
+            assert_eq!(loaded.regrowth, source.regrowth);
+            assert_eq!(loaded.appear, source.appear);
+            assert_eq!(loaded.vanish, source.vanish);
+            assert_eq!(loaded.initial, source.initial);
+            assert_eq!(loaded.tiles.len(), source.tiles.len());
+        }
+        assert!(!loaded.food_sources[1].active);
+        assert!(loaded.get(Pos::new(10, 0)).tile_type == TileType::Empty);
+    }
+
+    const ASCII_MAP: &str = "XXXXXXXXXX\nX@@.....XX\nX@...H...X\nX      @\nXXXXXXXXXX\n";
+
+    #[test]
+    fn ascii_map() {
+        let map = Map::from_ascii("Ascii", ASCII_MAP, &Config::default()).unwrap();
+        assert_eq!((*map.width(), *map.height()), (10, 5));
+        assert!(map.nests == vec![Pos::new(5, 2)]);
+        assert!(map.get(Pos::new(0, 0)).tile_type == TileType::Border);
+        assert!(map.get(Pos::new(9, 3)).tile_type == TileType::Empty);
+        assert_eq!(map.food_sources.len(), 2);
+        assert_eq!(map.food_sources[0].tiles.len(), 3);
+
+        let exported = map.to_ascii();
+        assert_eq!(exported.lines().nth(3), Some("X......@.."));
+        let reloaded = Map::from_ascii("Ascii", &exported, &Config::default()).unwrap();
+        assert_eq!(reloaded.to_ascii(), exported);
+
+        assert!(Map::from_ascii("Ascii", "XX\nX?", &Config::default()).is_err());
+        assert!(Map::from_ascii("Ascii", "XX\nX@", &Config::default()).is_err());
+        assert!(Map::from_ascii("Ascii", "HH.\nH..\n...", &Config::default()).is_err());
+        assert!(Map::from_ascii("Ascii", "HH.\nHH.\n...", &Config::default()).is_ok());
+    }
+
+    #[test]
+    fn diffusion() {
+        let mut map = Map::from_ron(MAP, &Config::default()).unwrap();
+        map.paint(Pos::new(1, 1), TileType::Border, 0);
+        map.drop_ph(0, PhType::FoodTrail, Pos::new(2, 2), 800., 1000.);
+        map.drop_ph(0, PhType::FoodTrail, Pos::new(3, 2), 800., 1000.);
+        map.diffuse(40);
+
+        let conc = |map: &Map, x, y| map.get(Pos::new(x, y)).concentrations()[0][1];
+        // both tiles spread from the same snapshot
+        assert_eq!(conc(&map, 2, 2), 800. - 7. * 40. + 40.);
+        assert_eq!(conc(&map, 3, 2), 800. - 8. * 40. + 40.);
+        assert_eq!(conc(&map, 2, 3), 80.);
+        assert_eq!(conc(&map, 1, 1), 0.);
+        assert_eq!(conc(&map, 4, 2), 40.);
+        assert!(map.get(Pos::new(3, 2)).ph(0, PhType::Exploration).is_none());
+        let total: f32 = map
+            .tile_matrix
+            .iter()
+            .flatten()
+            .map(|t| t.concentrations()[0][1])
+            .sum();
+        assert_eq!(total, 1600.);
+
+        // the spreading trail leaves no traces far away even without a threshold
+        for _ in 0..20 {
+            map.diffuse(10);
+            for tile in map.tile_matrix.iter_mut().flatten() {
+                tile.evaporate(Evaporation::Exponential(2.), 0.);
+            }
+        }
+        assert!(map.get(Pos::new(3, 3)).ph(0, PhType::FoodTrail).is_some());
+        assert!(!map.get(Pos::new(20, 5)).has_ph());
+    }
+
+    #[test]
+    fn nest_pheromone() {
+        let mut map = Map::from_ron(MAP, &Config::default()).unwrap();
+        map.drop_ph(0, PhType::FoodTrail, Pos::new(25, 7), 30., 1000.);
+        map.drop_ph(0, PhType::FoodTrail, Pos::new(24, 7), 60., 1000.);
+        map.drop_ph(0, PhType::Exploration, Pos::new(26, 7), 60., 1000.);
+        map.drop_ph(0, PhType::FoodTrail, Pos::new(25, 9), 60., 1000.);
+
+        assert_eq!(map.nest_ph(0, PhMetric::Sum, 1), 90.);
+        assert_eq!(map.nest_ph(0, PhMetric::Max, 1), 60.);
+        // only the row beneath the nest lies outside of it
+        assert_eq!(map.nest_ph(0, PhMetric::Mean, 1), 30.);
+        assert_eq!(map.nest_ph(0, PhMetric::Mean, 2), 9.);
+        assert_eq!(map.nest_ph(0, PhMetric::Sum, 3), 150.);
+    }
+
+    #[test]
+    fn evaporation_models() {
+        let mut map = Map::from_ron(MAP, &Config::default()).unwrap();
+        let pos = Pos::new(10, 1);
+        map.drop_ph(0, PhType::Exploration, pos, 3., 100.);
+        map.drop_ph(0, PhType::Exploration, pos, 500., 100.);
+        let conc = |map: &Map| map.get(pos).ph(0, PhType::Exploration).map(|ph| ph.conc());
+        assert_eq!(conc(&map), Some(100.));
+
+        map.get_mut(pos)
+            .evaporate(Evaporation::Exponential(10.), 1.);
+        assert_eq!(conc(&map), Some(90.));
+        map.get_mut(pos).evaporate(Evaporation::Linear(0.5), 1.);
+        assert_eq!(conc(&map), Some(89.5));
+        map.get_mut(pos).evaporate(Evaporation::HalfLife(1.), 1.);
+        assert_eq!(conc(&map), Some(44.75));
+        map.get_mut(pos).evaporate(Evaporation::Linear(4.), 41.);
+        assert_eq!(conc(&map), None);
+
+        // small drops stay with a low threshold
+        map.drop_ph(0, PhType::Exploration, pos, 2., 100.);
+        map.get_mut(pos)
+            .evaporate(Evaporation::Exponential(2.), 0.5);
+        assert_eq!(conc(&map), Some(1.96));
+    }
+
+    #[test]
+    fn boundary_modes() {
+        let mut map = Map::from_ron(MAP, &Config::default()).unwrap();
+        let corner = Pos::new(0, 0);
+        assert_eq!(map.get_neightbours(corner).len(), 3);
+        assert!(map.bound(Pos::new(-2, 12)) == Pos::new(0, 9));
+
+        map.boundary = Boundary::Wrap;
+        assert_eq!(map.get_neightbours(corner).len(), 8);
+        assert!(map.bound(Pos::new(-2, 12)) == Pos::new(28, 2));
+        assert_eq!(map.delta(Pos::new(1, 1), Pos::new(29, 9)), (-2, -2));
+        assert_eq!(map.dist(Pos::new(0, 5), Pos::new(29, 5)), 1.);
+
+        map.boundary = Boundary::Reflect;
+        assert_eq!(map.get_neightbours(corner).len(), 3);
+        assert!(map.bound(Pos::new(-2, 12)) == Pos::new(2, 6));
+    }
+
+    #[test]
+    fn terrain() {
+        let map = MAP.replace(
+            "areas: [",
+            "areas: [(area_type: Terrain, terrain: Mud, borders: [(func: \"4\", direction: Above)]),",
+        );
+        let mut map = Map::from_ron(&map, &Config::default()).unwrap();
+        // terrain areas keep food and nests
+        assert!(map.get(Pos::new(3, 5)).tile_type == TileType::Food);
+        assert!(map.get(Pos::new(3, 5)).terrain == Terrain::Mud);
+        assert!(map.get(Pos::new(25, 5)).tile_type == TileType::Nest);
+        assert!(map.get(Pos::new(3, 1)).terrain == Terrain::Ground);
+
+        map.paint(Pos::new(3, 5), TileType::Empty, 0);
+        assert!(map.get(Pos::new(3, 5)).terrain == Terrain::Mud);
+        map.set_terrain(Pos::new(3, 0), Terrain::Water);
+
+        let reloaded = Map::from_ascii("Ascii", &map.to_ascii(), &Config::default()).unwrap();
+        assert!(reloaded.get(Pos::new(3, 0)).terrain == Terrain::Water);
+        assert!(reloaded.get(Pos::new(3, 5)).terrain == Terrain::Mud);
+        assert_eq!(Terrain::Water.next(), Terrain::Ground);
+    }
+
+    #[test]
+    fn nest_footprint() {
+        let mut config = Config::default();
+        config.nest_entrances.set(2);
+        let map = Map::from_ron(MAP, &config).unwrap();
+        let nest = &map.colony_nests[0];
+        assert_eq!(nest.tiles().count(), 15);
+        assert!(map.get(Pos::new(23, 4)).tile_type == TileType::Nest);
+        assert!(map.get(Pos::new(27, 6)).tile_type == TileType::Nest);
+        assert!(nest.entrances == vec![Pos::new(25, 6), Pos::new(25, 4)]);
+
+        assert!(map.reached_nest(Pos::new(22, 3), 0));
+        assert!(map.reached_nest(Pos::new(28, 5), 0));
+        assert!(!map.reached_nest(Pos::new(21, 5), 0));
+
+        let ascii = "..........\n.HHH......\n.HHH...H..\n..........\n";
+        let map = Map::from_ascii("Ascii", ascii, &config).unwrap();
+        assert!(map.nests == vec![Pos::new(2, 1), Pos::new(7, 2)]);
+        assert_eq!(map.colony_nests[0].tiles().count(), 6);
+        assert!(map.colony_nests[1].entrances == vec![Pos::new(7, 2)]);
+    }
+
+    #[test]
+    fn invalid_map() {
+        let nest_outside = MAP.replace("(x: 25, y: 5)", "(x: 30, y: 5)");
+        assert!(Map::from_ron(&nest_outside, &Config::default()).is_err());
+
+        let no_nest = MAP.replace("(x: 25, y: 5)", "");
+        assert!(Map::from_ron(&no_nest, &Config::default()).is_err());
+
+        let bad_function = MAP.replace("\"8\"", "\"8*\"");
+        assert!(Map::from_ron(&bad_function, &Config::default()).is_err());
+    }
+}
@@ -1,2 +1,2 @@
-pub mod map;
-pub mod simulation;
+pub mod map;
+pub mod simulation;
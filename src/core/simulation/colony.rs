@@ -1,41 +1,111 @@
-use std::{collections::HashMap, ptr::hash};
+use std::{collections::HashMap, f32::consts::PI, ptr::hash};
 
 use nalgebra::{Point2, Vector2};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 
 use crate::{core::map::map::Pos, utils::AntDirection};
 
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct Colony {
+    /// Index of the colony, selects its nest and pheromone.
+    pub id: usize,
+    pub nest: Pos,
     pub ants: AntCollection,
+    /// Food delivered over the whole run.
     pub food: i32,
+    /// Food in the nest, eaten by hungry ants and spent on new ones.
+    pub store: i32,
+    pub born: i32,
+    pub died: i32,
 }
 
 impl Colony {
-    pub fn add_ant(&mut self, speed: i32, position: Pos, state: AntState) {
-        let direction: AntDirection = rand::random();
-        self.ants.push(speed, position, direction, state);
+    pub fn new(id: usize, nest: Pos) -> Self {
+        Self {
+            id,
+            nest,
+            ..Default::default()
+        }
+    }
+
+    /// Adds an ant heading into a random direction.
+    pub fn add_ant<R: Rng>(&mut self, speed: i32, position: Pos, state: AntState, rng: &mut R) {
+        let heading = rng.gen_range(0.0..2. * PI);
+        self.ants.push(speed, position, heading, state);
+    }
+
+    /// Ant count per state: seeker, returner, follower, noobs.
+    pub fn ant_count(&self) -> [i32; 4] {
+        let state_count = self.ants.state_count();
+        [
+            *state_count.get(&AntState::Seeker).unwrap_or(&0),
+            *state_count.get(&AntState::Returner).unwrap_or(&0),
+            *state_count.get(&AntState::Follower).unwrap_or(&0),
+            *state_count.get(&AntState::Noob).unwrap_or(&0),
+        ]
     }
 }
-#[derive(Default, Clone)]
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct AntCollection {
     pub id: Vec<i32>,
     pub speed: Vec<i32>,
     pub position: Vec<Pos>,
     pub direction: Vec<AntDirection>,
+    /// Heading in radians, `direction` is its grid step.
+    pub heading: Vec<f32>,
     pub state: Vec<AntState>,
+    /// Path an ant walked on its way out, for the path memory homing.
     pub travel: Vec<Vec<Pos>>,
+    /// Displacement from the nest as integrated by the ant, including its errors.
+    pub displacement: Vec<Vector2<f32>>,
+    /// Steps of a seeker since it left, or of a returner or noob searching for a nest
+    /// it missed.
     pub steps: Vec<i32>,
+    /// Movement points gathered on slow terrain.
+    pub progress: Vec<i32>,
+    /// Energy used up since the ant was last fed, it starves at `Config::ant_energy`.
+    pub hunger: Vec<i32>,
+    pub age: Vec<i32>,
+    /// Whether the ant may leave its tile this step.
+    pub moving: Vec<bool>,
+    /// Id of the next ant, ids are not reused after an ant died.
+    next_id: i32,
 }
 
 impl AntCollection {
-    pub fn push(&mut self, speed: i32, position: Pos, direction: AntDirection, state: AntState) {
-        self.id.push(self.id.len() as i32);
+    pub fn push(&mut self, speed: i32, position: Pos, heading: f32, state: AntState) {
+        self.id.push(self.next_id);
+        self.next_id += 1;
         self.speed.push(speed);
         self.position.push(position);
-        self.direction.push(direction);
+        self.direction.push(AntDirection::from_angle(heading));
+        self.heading.push(heading);
         self.state.push(state);
         self.travel.push(Vec::new());
+        self.displacement.push(Vector2::zeros());
         self.steps.push(0);
+        self.progress.push(0);
+        self.hunger.push(0);
+        self.age.push(0);
+        self.moving.push(true);
+    }
+
+    /// Removes an ant, the last ant takes its place.
+    pub fn remove(&mut self, index: usize) {
+        self.id.swap_remove(index);
+        self.speed.swap_remove(index);
+        self.position.swap_remove(index);
+        self.direction.swap_remove(index);
+        self.heading.swap_remove(index);
+        self.state.swap_remove(index);
+        self.travel.swap_remove(index);
+        self.displacement.swap_remove(index);
+        self.steps.swap_remove(index);
+        self.progress.swap_remove(index);
+        self.hunger.swap_remove(index);
+        self.age.swap_remove(index);
+        self.moving.swap_remove(index);
     }
 
     pub fn state_count(&self) -> HashMap<AntState, i32> {
@@ -69,7 +139,7 @@ impl AntCollection {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum AntState {
     Seeker,
     Returner,
@@ -1,2 +1,2 @@
-pub mod colony;
-pub mod simulation;
+pub mod colony;
+pub mod simulation;
@@ -1,283 +1,1327 @@
-use anyhow::Result;
-use itertools::izip;
-use rand::seq::IteratorRandom;
-use rand::Rng;
-use std::rc::Rc;
-
-use crate::{
-    config::{Config, SharedConfig},
-    core::map::map::{Map, PhType, Pos, TileType},
-};
-
-use super::colony::{AntCollection, AntState, Colony};
-
-#[derive(Default, Clone)]
-pub struct SimData {
-    pub step: Vec<i32>,
-    // seeker returner follower noobs
-    pub ants: Vec<[i32; 4]>,
-    pub food_total: Vec<i32>,
-    pub food_step: Vec<i32>,
-}
-
-impl SimData {
-    pub fn new() -> Self {
-        Self {
-            step: Vec::new(),
-            ants: Vec::new(),
-            food_total: Vec::new(),
-            food_step: Vec::new(),
-        }
-    }
-
-    pub fn push(&mut self, step: i32, ants: [i32; 4], food_total: i32, food_step: i32) {
-        self.step.push(step);
-        self.ants.push(ants);
-        self.food_total.push(food_total);
-        self.food_step.push(food_step)
-    }
-}
-
-#[derive(Default)]
-pub struct Simulation {
-    pub map: Map,
-    pub done: bool,
-    pub paused: bool,
-    pub colony: Colony,
-    pub history: SimData,
-    // environment config
-    config: Config,
-}
-
-impl Simulation {
-    pub fn new(map: Map) -> Self {
-        let mut colony = Colony::default();
-        let (x_mid, y_mid) = (map.width() / 2, map.height() / 2);
-        Self {
-            map,
-            done: false,
-            paused: false,
-            colony,
-            history: SimData::new(),
-            config: Config::init(),
-        }
-    }
-
-    pub fn reset(&mut self, config: Config) {
-        self.colony = Colony::default();
-        self.map = Map::new(
-            "Map",
-            self.config.map_width.val(),
-            self.config.map_height.val(),
-        );
-        self.config = config;
-        self.done = false;
-    }
-
-    pub fn spwan_ant(&mut self) {
-        let ant_state = Simulation::decide_ant_state(&self.map, &self.config);
-        self.colony.add_ant(1, self.map.nest_pos, ant_state);
-    }
-
-    fn decide_ant_state(map: &Map, config: &Config) -> AntState {
-        let neighbours = map.get_neightbours(map.nest_pos);
-        let average_phc = neighbours
-            .iter()
-            .filter_map(|t| t.pheromone.as_ref())
-            .map(|ph| ph.conc())
-            .sum::<i32>();
-        // / neighbours.len() as i32;
-
-        if average_phc >= config.min_ph_c.val() {
-            return AntState::Follower;
-        } else {
-            return AntState::Seeker;
-        }
-    }
-
-    pub fn step(&mut self) -> Result<()> {
-        // execute systems
-        Simulation::ant_system(&mut self.colony, &mut self.map, &self.config);
-        Simulation::ph_system(&mut self.map, &self.config);
-
-        // history log
-        let state_count = self.colony.ants.state_count();
-        let ants: [i32; 4] = [
-            *state_count.get(&AntState::Seeker).unwrap_or(&0),
-            *state_count.get(&AntState::Returner).unwrap_or(&0),
-            *state_count.get(&AntState::Follower).unwrap_or(&0),
-            *state_count.get(&AntState::Noob).unwrap_or(&0),
-        ];
-
-        let food_rate =
-            self.colony.food - self.history.food_total.last().cloned().unwrap_or_default();
-
-        self.history.push(
-            self.history.step.len() as i32,
-            ants,
-            self.colony.food,
-            food_rate,
-        );
-
-        Ok(())
-    }
-
-    fn ant_system(colony: &mut Colony, map: &mut Map, config: &Config) {
-        Simulation::seeker_system(colony, map, config);
-        Simulation::returner_system(colony, map, config);
-        Simulation::noob_system(colony, map, config);
-        Simulation::follower_system(colony, map, config);
-    }
-
-    fn ph_system(map: &mut Map, config: &Config) {
-        map.tile_matrix.iter_mut().for_each(|row| {
-            row.iter_mut()
-                .filter(|t| t.pheromone.is_some())
-                .for_each(|t| t.evaporate(config.evaporation_rate.val()));
-        })
-    }
-
-    fn returner_system(colony: &mut Colony, map: &mut Map, config: &Config) {
-        for (id, speed, position, direction, state) in izip!(
-            &colony.ants.id,
-            &colony.ants.speed,
-            &mut colony.ants.position,
-            &mut colony.ants.direction,
-            &mut colony.ants.state,
-        )
-        .filter(|(_id, _speed, _position, _direction, state)| **state == AntState::Returner)
-        {
-            let dir_x = (map.nest_pos.x - position.x).signum();
-            let dir_y = (map.nest_pos.y - position.y).signum();
-
-            let neighbours = map.get_neightbours(*position);
-            if let Some(tile) = neighbours
-                .iter()
-                .filter(|t| t.pheromone.is_some())
-                .find(|t| Pos::dist(*position, map.nest_pos) >= Pos::dist(t.pos, map.nest_pos))
-            {
-                *position = tile.pos;
-            } else {
-                if rand::thread_rng().gen::<f32>() <= 0.5 {
-                    *position = Pos::new(position.x + dir_x, position.y);
-                } else {
-                    *position = Pos::new(position.x, position.y + dir_y);
-                }
-            }
-
-            // drop ph
-            map.drop_ph(PhType::FoodTrail, *position, config.ph_drop.val());
-
-            if map.reached_nest(*position) {
-                *state = Simulation::decide_ant_state(map, config);
-                colony.food += 1;
-            }
-        }
-    }
-
-    fn follower_system(colony: &mut Colony, map: &mut Map, config: &Config) {
-        for (id, speed, position, direction, state) in izip!(
-            &colony.ants.id,
-            &colony.ants.speed,
-            &mut colony.ants.position,
-            &mut colony.ants.direction,
-            &mut colony.ants.state,
-        )
-        .filter(|(_id, _speed, _position, _direction, state)| **state == AntState::Follower)
-        {
-            let neighbours = map.get_neightbours(*position);
-            let max_ph_tile = neighbours
-                .iter()
-                .filter(|t| t.pheromone.is_some())
-                .filter(|t| Pos::dist(map.nest_pos, t.pos) >= Pos::dist(*position, map.nest_pos))
-                .choose(&mut rand::thread_rng());
-            // .max_by_key(|t| OrderedFloat(t.pheromone.unwrap().conc()));
-
-            if let Some(tile) = max_ph_tile {
-                *position = tile.pos;
-            } else {
-                *state = AntState::Seeker;
-            }
-
-            if let Some(tile) = neighbours.iter().find(|t| t.tile_type == TileType::Food) {
-                *state = AntState::Returner;
-                map.clear(tile.pos);
-            }
-        }
-    }
-
-    fn noob_system(colony: &mut Colony, map: &mut Map, config: &Config) {
-        for (id, speed, position, direction, state) in izip!(
-            &colony.ants.id,
-            &colony.ants.speed,
-            &mut colony.ants.position,
-            &mut colony.ants.direction,
-            &mut colony.ants.state,
-        )
-        .filter(|(_id, _speed, _position, _direction, state)| **state == AntState::Noob)
-        {
-            let dir_x = (map.nest_pos.x - position.x).signum();
-            let dir_y = (map.nest_pos.y - position.y).signum();
-
-            let neighbours = map.get_neightbours(*position);
-            if let Some(tile) = neighbours
-                .iter()
-                .find(|t| Pos::dist(*position, map.nest_pos) >= Pos::dist(t.pos, map.nest_pos))
-            {
-                *position = tile.pos;
-            } else {
-                if rand::thread_rng().gen::<f32>() <= 0.5 {
-                    *position = Pos::new(position.x + dir_x, position.y);
-                } else {
-                    *position = Pos::new(position.x, position.y + dir_y);
-                }
-            }
-
-            if map.reached_nest(*position) {
-                *state = Simulation::decide_ant_state(map, config);
-            }
-        }
-    }
-
-    fn seeker_system(colony: &mut Colony, map: &mut Map, config: &Config) {
-        for (id, speed, position, direction, state, steps) in izip!(
-            &colony.ants.id,
-            &colony.ants.speed,
-            &mut colony.ants.position,
-            &mut colony.ants.direction,
-            &mut colony.ants.state,
-            &mut colony.ants.steps,
-        )
-        .filter(|(_id, _speed, _position, _direction, state, _steps)| **state == AntState::Seeker)
-        {
-            // add steps counter
-            *steps += 1;
-
-            let mut rng = rand::thread_rng();
-            // rotate random
-            if rng.gen_ratio(1, 5) {
-                *direction = direction.random_turn(&mut rng);
-            };
-
-            let neighbours = map.get_neightbours(*position);
-
-            if let Some(tile) = neighbours.iter().find(|t| t.tile_type == TileType::Food) {
-                *state = AntState::Returner;
-                map.clear(tile.pos);
-                *steps = 0;
-            } else if *steps > config.max_steps.val() {
-                *state = AntState::Noob;
-                *steps = 0;
-            }
-
-            // calculate new position
-            let x_new = position.x + direction.vec().x * speed;
-            let y_new = position.y + direction.vec().y * speed;
-            let new_pos = Pos::new(
-                x_new.clamp(0, *map.width() - 1),
-                y_new.clamp(0, *map.height() - 1),
-            );
-            *position = new_pos;
-        }
-    }
-}
+use anyhow::{Context, Result};
+use itertools::izip;
+use nalgebra::Vector2;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::seq::IteratorRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use std::{f32::consts::PI, fs, path::Path, path::PathBuf, rc::Rc};
+
+use crate::{
+    config::{Config, SharedConfig},
+    core::map::map::{
+        Boundary, Evaporation, FoodSource, Map, Nest, PhMetric, PhType, Pos, Tile, TileType,
+    },
+    utils::{AntDirection, Movement},
+};
+
+use super::colony::{AntCollection, AntState, Colony};
+
+#[derive(Default, Clone, Serialize, Deserialize)]
+pub struct SimData {
+    pub step: Vec<i32>,
+    // seeker returner follower noobs
+    pub ants: Vec<[i32; 4]>,
+    pub food_total: Vec<i32>,
+    pub food_step: Vec<i32>,
+    // remaining food of every source
+    pub food_sources: Vec<Vec<i32>>,
+    // ant states and collected food of every colony
+    pub colony_ants: Vec<Vec<[i32; 4]>>,
+    pub colony_food: Vec<Vec<i32>>,
+    // food in the nest of every colony
+    #[serde(default)]
+    pub colony_store: Vec<Vec<i32>>,
+    // pheromone around the nest of every colony, see `Map::nest_ph`
+    #[serde(default)]
+    pub nest_ph: Vec<Vec<f32>>,
+    // config changes in the order they were applied
+    #[serde(default)]
+    pub config_changes: Vec<ConfigChange>,
+}
+
+/// A config parameter changed during a run, applied before `step`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ConfigChange {
+    pub step: i32,
+    pub name: String,
+    pub old: i32,
+    pub new: i32,
+}
+
+impl SimData {
+    pub fn new() -> Self {
+        Self {
+            step: Vec::new(),
+            ants: Vec::new(),
+            food_total: Vec::new(),
+            food_step: Vec::new(),
+            food_sources: Vec::new(),
+            colony_ants: Vec::new(),
+            colony_food: Vec::new(),
+            colony_store: Vec::new(),
+            nest_ph: Vec::new(),
+            config_changes: Vec::new(),
+        }
+    }
+
+    pub fn push(
+        &mut self,
+        step: i32,
+        ants: [i32; 4],
+        food_total: i32,
+        food_step: i32,
+        food_sources: Vec<i32>,
+        colony_ants: Vec<[i32; 4]>,
+        colony_food: Vec<i32>,
+        colony_store: Vec<i32>,
+        nest_ph: Vec<f32>,
+    ) {
+        self.step.push(step);
+        self.ants.push(ants);
+        self.food_total.push(food_total);
+        self.food_step.push(food_step);
+        self.food_sources.push(food_sources);
+        self.colony_ants.push(colony_ants);
+        self.colony_food.push(colony_food);
+        self.colony_store.push(colony_store);
+        self.nest_ph.push(nest_ph);
+    }
+}
+
+pub struct Simulation {
+    pub map: Map,
+    pub done: bool,
+    pub paused: bool,
+    pub colonies: Vec<Colony>,
+    pub history: SimData,
+    /// Map file the simulation is (re)loaded from; a random map is generated if `None`.
+    pub map_path: Option<PathBuf>,
+    // environment config
+    config: Config,
+    /// Drives every random decision of the ants, saved with the simulation.
+    rng: ChaCha8Rng,
+}
+
+impl Default for Simulation {
+    fn default() -> Self {
+        Simulation::new(Map::default())
+    }
+}
+
+/// How returners and noobs find back to the nest, selected via `Config::homing`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Homing {
+    /// Follow the exploration pheromone or head straight for the nest.
+    Beeline,
+    /// Walk the remembered path backwards.
+    PathMemory,
+    /// Head for where the integrated displacement says the nest is and search around there.
+    PathIntegration,
+}
+
+impl Homing {
+    pub fn from_config(config: &Config) -> Self {
+        match config.homing.val() {
+            1 => Homing::PathMemory,
+            2 => Homing::PathIntegration,
+            _ => Homing::Beeline,
+        }
+    }
+}
+
+/// Config of a saved simulation, the name and value of every parameter matched by name when
+/// loading.
+#[derive(Serialize, Deserialize)]
+#[serde(transparent)]
+struct SavedConfig(Vec<(String, i32)>);
+
+impl SavedConfig {
+    fn new(config: &Config) -> Self {
+        SavedConfig(
+            config
+                .vars()
+                .iter()
+                .map(|var| (var.name().to_string(), var.val()))
+                .collect(),
+        )
+    }
+
+    /// Sets the saved values, parameters missing in the save keep their default.
+    fn apply(self, config: &mut Config) {
+        for var in config.vars_mut() {
+            if let Some((_, val)) = self.0.iter().find(|(name, _)| name == var.name()) {
+                var.set(*val);
+            }
+        }
+    }
+}
+
+/// Everything needed to continue a simulation, see `Simulation::save`.
+#[derive(Serialize, Deserialize)]
+struct SaveState {
+    config: SavedConfig,
+    map: Map,
+    colony_nests: Vec<Nest>,
+    tiles: Vec<Vec<Tile>>,
+    food_sources: Vec<FoodSource>,
+    colonies: Vec<Colony>,
+    history: SimData,
+    rng: ChaCha8Rng,
+    map_path: Option<PathBuf>,
+    done: bool,
+}
+
+impl Simulation {
+    pub fn new(map: Map) -> Self {
+        let colonies = Simulation::init_colonies(&map);
+        Self {
+            map,
+            done: false,
+            paused: false,
+            colonies,
+            history: SimData::new(),
+            map_path: None,
+            config: Config::init(),
+            rng: ChaCha8Rng::from_entropy(),
+        }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Writes the whole simulation state to a RON file: map, pheromones, ants, history,
+    /// config and the random number generator.
+    pub fn save<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let state = SaveState {
+            config: SavedConfig::new(&self.config),
+            colony_nests: self.map.colony_nests.clone(),
+            tiles: self.map.tile_matrix.clone(),
+            food_sources: self.map.food_sources.clone(),
+            map: std::mem::take(&mut self.map),
+            colonies: std::mem::take(&mut self.colonies),
+            history: std::mem::take(&mut self.history),
+            rng: self.rng.clone(),
+            map_path: self.map_path.clone(),
+            done: self.done,
+        };
+        let contents = ron::ser::to_string(&state);
+        self.map = state.map;
+        self.colonies = state.colonies;
+        self.history = state.history;
+
+        let contents = contents.context("failed serializing simulation")?;
+        fs::write(path, contents)
+            .with_context(|| format!("failed writing save file {}", path.display()))
+    }
+
+    /// Restores a simulation written by `Simulation::save`. The loaded simulation is paused.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed reading save file {}", path.display()))?;
+        let state: SaveState = ron::from_str(&contents)
+            .with_context(|| format!("failed parsing save file {}", path.display()))?;
+
+        let mut config = Config::init();
+        state.config.apply(&mut config);
+        let colonies = state.colonies;
+        let mut map = state.map;
+        map.colony_nests = state.colony_nests;
+        map.tile_matrix = state.tiles;
+        map.food_sources = state.food_sources;
+        map.boundary = Boundary::from_config(&config);
+        map.compute_distances();
+
+        Ok(Self {
+            map,
+            done: state.done,
+            paused: true,
+            colonies,
+            history: state.history,
+            map_path: state.map_path,
+            config,
+            rng: state.rng,
+        })
+    }
+
+    pub fn from_file(path: PathBuf, config: &Config) -> Result<Self> {
+        let map = Map::load(&path, config)?;
+        let mut simulation = Simulation::new(map);
+        simulation.map_path = Some(path);
+        Ok(simulation)
+    }
+
+    pub fn reset(&mut self, config: Config) -> Result<()> {
+        self.map = match &self.map_path {
+            Some(path) => Map::load(path, &config)?,
+            None => Map::new("Map", &config),
+        };
+        self.colonies = Simulation::init_colonies(&self.map);
+        self.record_config(&config, false);
+        self.done = false;
+        Ok(())
+    }
+
+    /// Takes over the behavioural parameters of the config right away, the structural
+    /// ones wait for `reset`.
+    pub fn apply_config(&mut self, config: &Config) {
+        self.record_config(config, true);
+    }
+
+    /// Whether the parameter at `index` of `Config::vars` differs from the running simulation.
+    pub fn requires_reset(&self, config: &Config, index: usize) -> bool {
+        self.config.vars()[index].val() != config.vars()[index].val()
+    }
+
+    /// Applies the changed parameters of the config and records them in the history.
+    fn record_config(&mut self, config: &Config, live_only: bool) {
+        let step = self.history.step.len() as i32;
+        for (var, new) in self.config.vars_mut().into_iter().zip(config.vars()) {
+            if var.val() == new.val() || (live_only && !var.is_live()) {
+                continue;
+            }
+            self.history.config_changes.push(ConfigChange {
+                step,
+                name: var.name().to_string(),
+                old: var.val(),
+                new: new.val(),
+            });
+            var.set(new.val());
+        }
+    }
+
+    /// One colony per nest of the map.
+    fn init_colonies(map: &Map) -> Vec<Colony> {
+        map.nests
+            .iter()
+            .enumerate()
+            .map(|(id, nest)| Colony::new(id, *nest))
+            .collect()
+    }
+
+    pub fn spwan_ant(&mut self) {
+        for colony in self.colonies.iter_mut() {
+            Simulation::spawn(colony, &self.map, &self.config, &mut self.rng);
+        }
+    }
+
+    fn spawn(colony: &mut Colony, map: &Map, config: &Config, rng: &mut ChaCha8Rng) {
+        let ant_state = Simulation::decide_ant_state(map, config, colony.id);
+        // ants leave through a random entrance
+        let entrance = map.colony_nests[colony.id]
+            .entrances
+            .iter()
+            .choose(rng)
+            .cloned()
+            .unwrap_or(colony.nest);
+        colony.add_ant(1, entrance, ant_state, rng);
+    }
+
+    /// Pheromone around all entrances of the nest, as set up in the config.
+    fn nest_ph(map: &Map, config: &Config, colony: usize) -> f32 {
+        map.nest_ph(
+            colony,
+            PhMetric::from_config(config),
+            config.nest_ph_radius.val(),
+        )
+    }
+
+    fn decide_ant_state(map: &Map, config: &Config, colony: usize) -> AntState {
+        if Simulation::nest_ph(map, config, colony) >= config.min_ph_c.val() as f32 {
+            return AntState::Follower;
+        } else {
+            return AntState::Seeker;
+        }
+    }
+
+    pub fn step(&mut self) -> Result<()> {
+        // execute systems
+        for colony in self.colonies.iter_mut() {
+            Simulation::ant_system(colony, &mut self.map, &self.config, &mut self.rng);
+            Simulation::brood_system(colony, &self.map, &self.config, &mut self.rng);
+        }
+        Simulation::ph_system(&mut self.map, &self.config);
+        self.map.update_food(self.history.step.len() as i32);
+
+        // history log
+        let colony_ants: Vec<[i32; 4]> = self.colonies.iter().map(|c| c.ant_count()).collect();
+        let mut ants = [0; 4];
+        for count in colony_ants.iter() {
+            for (total, n) in ants.iter_mut().zip(count.iter()) {
+                *total += n;
+            }
+        }
+
+        let colony_food: Vec<i32> = self.colonies.iter().map(|c| c.food).collect();
+        let colony_store = self.colonies.iter().map(|c| c.store).collect();
+        let food_total = colony_food.iter().sum::<i32>();
+        let food_rate = food_total - self.history.food_total.last().cloned().unwrap_or_default();
+
+        let food_sources = self
+            .map
+            .food_sources
+            .iter()
+            .map(|source| source.remaining)
+            .collect();
+
+        let nest_ph = self
+            .colonies
+            .iter()
+            .map(|colony| Simulation::nest_ph(&self.map, &self.config, colony.id))
+            .collect();
+
+        self.history.push(
+            self.history.step.len() as i32,
+            ants,
+            food_total,
+            food_rate,
+            food_sources,
+            colony_ants,
+            colony_food,
+            colony_store,
+            nest_ph,
+        );
+
+        Ok(())
+    }
+
+    fn ant_system(colony: &mut Colony, map: &mut Map, config: &Config, rng: &mut ChaCha8Rng) {
+        let positions = colony.ants.position.clone();
+        Simulation::terrain_system(colony, map);
+        Simulation::seeker_system(colony, map, config, rng);
+        Simulation::returner_system(colony, map, config, rng);
+        Simulation::noob_system(colony, map, config, rng);
+        Simulation::follower_system(colony, map, config, rng);
+        Simulation::path_integration_system(colony, map, config, rng, &positions);
+        Simulation::energy_system(colony, map, config);
+    }
+
+    /// Ants use up a unit of energy every step and eat from the store whenever they pass
+    /// the nest, only whole pieces of food they can use up. Seekers and followers turn back
+    /// once their energy just lasts for the way home. Starved and old ants die.
+    /// Without an `ant_energy` the ants neither eat nor starve.
+    fn energy_system(colony: &mut Colony, map: &Map, config: &Config) {
+        let (energy, food_energy) = (config.ant_energy.val(), config.food_energy.val());
+        let mut dead = Vec::new();
+        for (index, (position, state, hunger, age)) in izip!(
+            &colony.ants.position,
+            &mut colony.ants.state,
+            &mut colony.ants.hunger,
+            &mut colony.ants.age,
+        )
+        .enumerate()
+        {
+            *age += 1;
+
+            if energy > 0 {
+                *hunger += 1;
+                if map.reached_nest(*position, colony.id) {
+                    let eaten = (*hunger / food_energy).min(colony.store);
+                    colony.store -= eaten;
+                    *hunger -= eaten * food_energy;
+                } else if matches!(*state, AntState::Seeker | AntState::Follower)
+                    && map
+                        .nest_distance(colony.id, *position)
+                        .is_some_and(|d| energy - *hunger <= d as i32)
+                {
+                    *state = AntState::Noob;
+                }
+            }
+
+            let max_age = config.max_age.val();
+            if (energy > 0 && *hunger >= energy) || (max_age > 0 && *age >= max_age) {
+                dead.push(index);
+            }
+        }
+
+        colony.died += dead.len() as i32;
+        for index in dead.into_iter().rev() {
+            colony.ants.remove(index);
+        }
+    }
+
+    /// The nest raises a new ant each step from the food it has beyond its reserve.
+    fn brood_system(colony: &mut Colony, map: &Map, config: &Config, rng: &mut ChaCha8Rng) {
+        let cost = config.ant_cost.val();
+        if cost > 0 && colony.store >= cost + config.food_reserve.val() {
+            colony.store -= cost;
+            colony.born += 1;
+            Simulation::spawn(colony, map, config, rng);
+        }
+    }
+
+    /// Ants add every step to their displacement from the nest. Each step is off by up to
+    /// the configured noise, so the error grows with the length of the trip.
+    /// Next to the nest the ants see where they are and start over.
+    fn path_integration_system(
+        colony: &mut Colony,
+        map: &Map,
+        config: &Config,
+        rng: &mut ChaCha8Rng,
+        previous: &[Pos],
+    ) {
+        let noise = config.integration_noise.val() as f32 / 100.;
+        for (position, previous, displacement) in izip!(
+            &colony.ants.position,
+            previous,
+            &mut colony.ants.displacement,
+        ) {
+            if map.reached_nest(*position, colony.id) {
+                *displacement = Vector2::zeros();
+                continue;
+            }
+            let (dx, dy) = map.delta(*previous, *position);
+            let step = Vector2::new(dx as f32, dy as f32);
+            let error = Vector2::new(rng.gen_range(-1.0..=1.0), rng.gen_range(-1.0..=1.0));
+            *displacement += step + error * noise * step.norm();
+        }
+    }
+
+    /// Next step of an ant homing by path integration, towards where its displacement places
+    /// the nest. An ant that got there without finding the nest or cannot get any closer
+    /// searches around that point, `searched` counts the steps of the search and widens it.
+    fn integrate_home(
+        map: &Map,
+        position: Pos,
+        displacement: Vector2<f32>,
+        searched: &mut i32,
+        rng: &mut ChaCha8Rng,
+    ) -> Pos {
+        let estimate = map.bound(Pos::new(
+            (position.x as f32 - displacement.x).round() as i32,
+            (position.y as f32 - displacement.y).round() as i32,
+        ));
+        if *searched == 0 && displacement.norm() >= 1. {
+            let step = Simulation::step_towards(map, position, estimate, rng);
+            if map.dist(step, estimate) < map.dist(position, estimate) {
+                return step;
+            }
+        }
+
+        // random walk within a radius around the estimate that grows with the search
+        *searched += 1;
+        let radius = 1. + (*searched as f32).sqrt();
+        map.get_neightbours(position)
+            .iter()
+            .filter(|t| map.can_step(position, t.pos) && map.dist(estimate, t.pos) <= radius)
+            .map(|t| t.pos)
+            .choose(rng)
+            .unwrap_or_else(|| Simulation::step_towards(map, position, estimate, rng))
+    }
+
+    /// Ants gain their speed as movement points each step and may only leave their tile once
+    /// the points cover the terrain cost.
+    fn terrain_system(colony: &mut Colony, map: &Map) {
+        for (speed, position, progress, moving) in izip!(
+            &colony.ants.speed,
+            &colony.ants.position,
+            &mut colony.ants.progress,
+            &mut colony.ants.moving,
+        ) {
+            *progress += speed;
+            *moving = *progress >= map.get(*position).terrain.cost();
+            if *moving {
+                *progress = 0;
+            }
+        }
+    }
+
+    fn ph_system(map: &mut Map, config: &Config) {
+        map.diffuse(config.diffusion.val());
+        let evaporation = Evaporation::from_config(config);
+        let threshold = config.ph_threshold.val() as f32;
+        map.tile_matrix.iter_mut().for_each(|row| {
+            row.iter_mut()
+                .filter(|t| t.has_ph())
+                .for_each(|t| t.evaporate(evaporation, threshold));
+        })
+    }
+
+    fn returner_system(colony: &mut Colony, map: &mut Map, config: &Config, rng: &mut ChaCha8Rng) {
+        let (colony_id, nest) = (colony.id, colony.nest);
+        let homing = Homing::from_config(config);
+        for (id, speed, position, direction, state, steps, travel, displacement, _moving) in izip!(
+            &colony.ants.id,
+            &colony.ants.speed,
+            &mut colony.ants.position,
+            &mut colony.ants.direction,
+            &mut colony.ants.state,
+            &mut colony.ants.steps,
+            &mut colony.ants.travel,
+            &colony.ants.displacement,
+            &colony.ants.moving,
+        )
+        .filter(
+            |(
+                _id,
+                _speed,
+                _position,
+                _direction,
+                state,
+                _steps,
+                _travel,
+                _displacement,
+                moving,
+            )| { **state == AntState::Returner && **moving },
+        ) {
+            // walk the remembered path, head for the estimated nest
+            // or follow the home trail of the seekers
+            let neighbours = map.get_neightbours(*position);
+            if let Some(pos) = Simulation::retrace(map, homing, *position, travel) {
+                *position = pos;
+            } else if homing == Homing::PathIntegration {
+                *position = Simulation::integrate_home(map, *position, *displacement, steps, rng);
+            } else if let Some(tile) = neighbours
+                .iter()
+                .filter(|t| {
+                    t.ph(colony_id, PhType::Exploration).is_some() && map.can_step(*position, t.pos)
+                })
+                .find(|t| Simulation::closer_to_nest(map, colony_id, *position, t.pos))
+            {
+                *position = tile.pos;
+            } else {
+                *position = Simulation::descend(map, colony_id, *position, rng)
+                    .unwrap_or_else(|| Simulation::step_towards(map, *position, nest, rng));
+            }
+
+            // drop ph
+            map.drop_ph(
+                colony_id,
+                PhType::FoodTrail,
+                *position,
+                config.ph_drop.val() as f32,
+                config.ph_cap.val() as f32,
+            );
+
+            if map.reached_nest(*position, colony_id) {
+                *state = Simulation::decide_ant_state(map, config, colony_id);
+                *steps = 0;
+                travel.clear();
+                colony.food += 1;
+                colony.store += 1;
+            }
+        }
+    }
+
+    fn follower_system(colony: &mut Colony, map: &mut Map, config: &Config, rng: &mut ChaCha8Rng) {
+        let (colony_id, nest) = (colony.id, colony.nest);
+        let loop_removal = config.loop_removal.val() == 1;
+        for (id, speed, position, direction, state, travel, _moving) in izip!(
+            &colony.ants.id,
+            &colony.ants.speed,
+            &mut colony.ants.position,
+            &mut colony.ants.direction,
+            &mut colony.ants.state,
+            &mut colony.ants.travel,
+            &colony.ants.moving,
+        )
+        .filter(
+            |(_id, _speed, _position, _direction, state, _travel, moving)| {
+                **state == AntState::Follower && **moving
+            },
+        ) {
+            let neighbours = map.get_neightbours(*position);
+            if let Some(pos) =
+                Simulation::follow_trail(map, config, colony_id, nest, *position, rng)
+            {
+                *position = pos;
+                Simulation::remember(travel, pos, loop_removal);
+            } else {
+                *state = AntState::Seeker;
+            }
+
+            if let Some(tile) = neighbours.iter().find(|t| t.tile_type == TileType::Food) {
+                if map.take_food(tile.pos) {
+                    *state = AntState::Returner;
+                }
+            }
+        }
+    }
+
+    /// Next step of a follower, chosen with the ant colony rule: the chance of a neighbour
+    /// is proportional to concentration^alpha * heuristic^beta, the heuristic prefers steps
+    /// away from the nest. Only neighbours not leading back to the nest are considered.
+    /// With the exploration rate the ant ignores the trail and takes any of them.
+    fn follow_trail(
+        map: &Map,
+        config: &Config,
+        colony: usize,
+        nest: Pos,
+        position: Pos,
+        rng: &mut ChaCha8Rng,
+    ) -> Option<Pos> {
+        let candidates: Vec<Tile> = map
+            .get_neightbours(position)
+            .into_iter()
+            .filter(|t| {
+                map.can_step(position, t.pos) && map.dist(nest, t.pos) >= map.dist(nest, position)
+            })
+            .collect();
+        if rng.gen_range(0..100) < config.exploration_rate.val() {
+            return candidates.iter().choose(rng).map(|t| t.pos);
+        }
+
+        let alpha = config.alpha.val() as f32 / 10.;
+        let beta = config.beta.val() as f32 / 10.;
+        let trail: Vec<(Pos, f32)> = candidates
+            .iter()
+            .filter_map(|t| {
+                let conc = t.ph(colony, PhType::FoodTrail)?.conc();
+                let heuristic = map.dist(nest, t.pos) - map.dist(nest, position) + 1.5;
+                Some((t.pos, conc.powf(alpha) * heuristic.powf(beta)))
+            })
+            .collect();
+        match WeightedIndex::new(trail.iter().map(|(_, weight)| *weight)) {
+            Ok(index) => Some(trail[index.sample(rng)].0),
+            // weights out of range, every tile on the trail is equally likely
+            Err(_) => trail.iter().choose(rng).map(|(pos, _)| *pos),
+        }
+    }
+
+    fn noob_system(colony: &mut Colony, map: &mut Map, config: &Config, rng: &mut ChaCha8Rng) {
+        let (colony_id, nest) = (colony.id, colony.nest);
+        let homing = Homing::from_config(config);
+        for (id, speed, position, direction, state, steps, travel, displacement, _moving) in izip!(
+            &colony.ants.id,
+            &colony.ants.speed,
+            &mut colony.ants.position,
+            &mut colony.ants.direction,
+            &mut colony.ants.state,
+            &mut colony.ants.steps,
+            &mut colony.ants.travel,
+            &colony.ants.displacement,
+            &colony.ants.moving,
+        )
+        .filter(
+            |(
+                _id,
+                _speed,
+                _position,
+                _direction,
+                state,
+                _steps,
+                _travel,
+                _displacement,
+                moving,
+            )| { **state == AntState::Noob && **moving },
+        ) {
+            if let Some(pos) = Simulation::retrace(map, homing, *position, travel) {
+                *position = pos;
+            } else if homing == Homing::PathIntegration {
+                *position = Simulation::integrate_home(map, *position, *displacement, steps, rng);
+            } else {
+                *position = Simulation::descend(map, colony_id, *position, rng)
+                    .unwrap_or_else(|| Simulation::step_towards(map, *position, nest, rng));
+            }
+
+            if map.reached_nest(*position, colony_id) {
+                *state = Simulation::decide_ant_state(map, config, colony_id);
+                *steps = 0;
+                travel.clear();
+            }
+        }
+    }
+
+    /// Remembers a step of an ant heading out. If the ant has been there before,
+    /// the loop can be cut out of the path.
+    fn remember(travel: &mut Vec<Pos>, pos: Pos, loop_removal: bool) {
+        if travel.last() == Some(&pos) {
+            return;
+        }
+        if loop_removal {
+            if let Some(i) = travel.iter().position(|p| *p == pos) {
+                travel.truncate(i + 1);
+                return;
+            }
+        }
+        travel.push(pos);
+    }
+
+    /// Next step home along the remembered path. `None` if the ants home otherwise,
+    /// the path is used up or an obstacle blocks it.
+    fn retrace(map: &Map, homing: Homing, position: Pos, travel: &mut Vec<Pos>) -> Option<Pos> {
+        if homing != Homing::PathMemory {
+            return None;
+        }
+        while let Some(pos) = travel.pop() {
+            if pos == position {
+                continue;
+            }
+            if map.passable(pos) {
+                return Some(pos);
+            }
+            travel.clear();
+        }
+        None
+    }
+
+    fn seeker_system(colony: &mut Colony, map: &mut Map, config: &Config, rng: &mut ChaCha8Rng) {
+        let (colony_id, nest) = (colony.id, colony.nest);
+        let movement = Movement::from_config(config);
+        let loop_removal = config.loop_removal.val() == 1;
+        for (id, speed, position, direction, heading, state, steps, travel, _moving) in izip!(
+            &colony.ants.id,
+            &colony.ants.speed,
+            &mut colony.ants.position,
+            &mut colony.ants.direction,
+            &mut colony.ants.heading,
+            &mut colony.ants.state,
+            &mut colony.ants.steps,
+            &mut colony.ants.travel,
+            &colony.ants.moving,
+        )
+        .filter(
+            |(_id, _speed, _position, _direction, _heading, state, _steps, _travel, moving)| {
+                **state == AntState::Seeker && **moving
+            },
+        ) {
+            // add steps counter
+            *steps += 1;
+
+            // turn away from the previous heading
+            if rng.gen_range(0..100) < config.turn_probability.val() {
+                *heading = movement.turn(*heading, config.turn_angle.val(), rng);
+            };
+            *direction = movement.quantize(*heading);
+
+            let neighbours = map.get_neightbours(*position);
+
+            let food_tile = neighbours.iter().find(|t| t.tile_type == TileType::Food);
+            if food_tile.map_or(false, |t| map.take_food(t.pos)) {
+                *state = AntState::Returner;
+                *steps = 0;
+            } else if *steps > config.max_steps.val() {
+                *state = AntState::Noob;
+                *steps = 0;
+            } else if neighbours.iter().any(|t| {
+                t.ph(colony_id, PhType::FoodTrail).is_some()
+                    && map.dist(nest, t.pos) >= map.dist(nest, *position)
+            }) {
+                // found a trail leading to food
+                *state = AntState::Follower;
+                *steps = 0;
+            }
+
+            // bounce off the map edge
+            if map.boundary == Boundary::Reflect {
+                let x_new = position.x + direction.vec().x * *speed;
+                let y_new = position.y + direction.vec().y * *speed;
+                if !map.in_bounds(Pos::new(x_new, y_new)) {
+                    *heading = (*heading + PI).rem_euclid(2. * PI);
+                    *direction = movement.quantize(*heading);
+                }
+            }
+
+            // calculate new position
+            let new_pos = match Simulation::seeker_target(map, *position, *direction, *speed) {
+                Some(pos) => pos,
+                // blocked by an obstacle, turn towards a free direction
+                None => match movement
+                    .directions()
+                    .filter_map(|d| {
+                        Simulation::seeker_target(map, *position, d, *speed).map(|pos| (d, pos))
+                    })
+                    .choose(rng)
+                {
+                    Some((free, pos)) => {
+                        *direction = free;
+                        *heading = free.angle();
+                        pos
+                    }
+                    None => *position,
+                },
+            };
+            *position = new_pos;
+            Simulation::remember(travel, new_pos, loop_removal);
+
+            // mark the way back home
+            map.drop_ph(
+                colony_id,
+                PhType::Exploration,
+                *position,
+                config.ph_drop.val() as f32,
+                config.ph_cap.val() as f32,
+            );
+        }
+    }
+
+    /// Tile a seeker reaches walking `speed` tiles into `direction`. `None` if an obstacle lies
+    /// on the way or a diagonal step squeezes between two obstacles touching at a corner.
+    fn seeker_target(map: &Map, position: Pos, direction: AntDirection, speed: i32) -> Option<Pos> {
+        let step = direction.vec();
+        let mut pos = position;
+        for _ in 0..speed {
+            let next = map.bound(Pos::new(pos.x + step.x, pos.y + step.y));
+            if !map.can_step(pos, next) {
+                return None;
+            }
+            pos = next;
+        }
+        Some(pos)
+    }
+
+    /// Step down the distance field of the nest, choosing randomly between equally close tiles.
+    /// `None` if the ant is cut off from the nest.
+    fn descend(map: &Map, colony: usize, position: Pos, rng: &mut ChaCha8Rng) -> Option<Pos> {
+        let neighbours = map.get_neightbours(position);
+        let distances = neighbours
+            .iter()
+            .filter(|t| map.can_step(position, t.pos))
+            .filter_map(|t| map.nest_distance(colony, t.pos).map(|d| (t.pos, d)));
+        let closest = distances.clone().map(|(_, d)| d).min()?;
+        distances
+            .filter(|(_, d)| *d == closest)
+            .map(|(pos, _)| pos)
+            .choose(rng)
+    }
+
+    /// Whether `to` lies no further from the nest than `from`, around obstacles if possible.
+    fn closer_to_nest(map: &Map, colony: usize, from: Pos, to: Pos) -> bool {
+        match (
+            map.nest_distance(colony, from),
+            map.nest_distance(colony, to),
+        ) {
+            (Some(from), Some(to)) => to <= from,
+            _ => map.dist(from, map.nests[colony]) >= map.dist(to, map.nests[colony]),
+        }
+    }
+
+    /// Moves one tile towards `target`, randomly along the x or the y axis.
+    /// If both are blocked, the ant steps onto a random passable neighbour.
+    fn step_towards(map: &Map, position: Pos, target: Pos, rng: &mut ChaCha8Rng) -> Pos {
+        let (dx, dy) = map.delta(position, target);
+
+        let mut steps = [
+            map.bound(Pos::new(position.x + dx.signum(), position.y)),
+            map.bound(Pos::new(position.x, position.y + dy.signum())),
+        ];
+        if rng.gen::<f32>() > 0.5 {
+            steps.swap(0, 1);
+        }
+
+        if let Some(step) = steps.iter().find(|p| **p != position && map.passable(**p)) {
+            return *step;
+        }
+
+        map.get_neightbours(position)
+            .iter()
+            .filter(|t| map.can_step(position, t.pos))
+            .map(|t| t.pos)
+            .choose(rng)
+            .unwrap_or(position)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::{SavedConfig, Simulation};
+    use crate::{
+        config::Config,
+        core::{
+            map::map::{Map, PhType, Pos},
+            simulation::colony::AntState,
+        },
+        utils::AntDirection,
+    };
+    use nalgebra::Vector2;
+
+    // 9x5 map with a vertical wall at x = 4, open only in the bottom row
+    const WALL_MAP: &str = r#"(
+        name: "Wall",
+        width: 9,
+        height: 5,
+        nests: [(x: 7, y: 2)],
+        areas: [
+            (
+                area_type: Obstacle,
+                borders: [
+                    (func: "100*(x-3.5)", direction: Beneath),
+                    (func: "-100*(x-4.5)", direction: Beneath),
+                    (func: "3", direction: Beneath),
+                ],
+            ),
+        ],
+    )"#;
+
+    const OPEN_MAP: &str = r#"(
+        name: "Open",
+        width: 30,
+        height: 20,
+        nests: [(x: 25, y: 10)],
+        areas: [],
+    )"#;
+
+    #[test]
+    fn step_around_wall() {
+        let map = Map::from_ron(WALL_MAP, &Config::default()).unwrap();
+        assert!(!map.passable(Pos::new(4, 2)));
+        assert!(map.passable(Pos::new(4, 4)));
+
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        for _ in 0..20 {
+            let pos = Simulation::step_towards(&map, Pos::new(3, 2), map.nests[0], &mut rng);
+            assert!(map.passable(pos));
+            assert!(pos != Pos::new(3, 2));
+        }
+    }
+
+    #[test]
+    fn two_trails() {
+        let map = Map::from_ron(WALL_MAP, &Config::default()).unwrap();
+        let mut simulation = Simulation::new(map);
+        let colony = &mut simulation.colonies[0];
+        colony.add_ant(1, Pos::new(1, 1), AntState::Seeker, &mut simulation.rng);
+        colony.add_ant(1, Pos::new(2, 4), AntState::Returner, &mut simulation.rng);
+        simulation.step().unwrap();
+
+        // seekers mark the way home, returners the way to the food
+        let positions = simulation.colonies[0].ants.position.clone();
+        let seeker = simulation.map.get(positions[0]);
+        assert!(seeker.ph(0, PhType::Exploration).is_some());
+        assert!(seeker.ph(0, PhType::FoodTrail).is_none());
+        let returner = simulation.map.get(positions[1]);
+        assert!(returner.ph(0, PhType::FoodTrail).is_some());
+        assert!(returner.ph(0, PhType::Exploration).is_none());
+    }
+
+    #[test]
+    fn weighted_trail() {
+        let mut config = Config::default();
+        let mut map = Map::from_ron(WALL_MAP, &config).unwrap();
+        let nest = map.nests[0];
+        let (weak, strong) = (Pos::new(1, 3), Pos::new(1, 1));
+        map.drop_ph(0, PhType::FoodTrail, weak, 10., 1000.);
+        map.drop_ph(0, PhType::FoodTrail, strong, 100., 1000.);
+
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let mut follow = |config: &Config| {
+            (0..50)
+                .map(|_| {
+                    Simulation::follow_trail(&map, config, 0, nest, Pos::new(2, 2), &mut rng)
+                        .unwrap()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // the strong trail wins by 10^5
+        config.alpha.set(50);
+        assert!(follow(&config).iter().all(|pos| *pos == strong));
+        config.alpha.set(0);
+        config.beta.set(0);
+        assert!(follow(&config).contains(&weak));
+        // tiles without pheromone are only taken while exploring
+        assert!(follow(&config)
+            .iter()
+            .all(|pos| *pos == weak || *pos == strong));
+        config.exploration_rate.set(100);
+        assert!(!follow(&config)
+            .iter()
+            .all(|pos| *pos == weak || *pos == strong));
+    }
+
+    #[test]
+    fn path_memory() {
+        let mut travel = Vec::new();
+        for (x, y) in [(1, 1), (2, 1), (2, 2), (1, 2), (2, 1), (3, 1)] {
+            Simulation::remember(&mut travel, Pos::new(x, y), true);
+        }
+        assert!(travel == vec![Pos::new(1, 1), Pos::new(2, 1), Pos::new(3, 1)]);
+
+        let mut config = Config::default();
+        config.homing.set(1);
+        let map = Map::from_ron(WALL_MAP, &config).unwrap();
+        let mut simulation = Simulation::new(map);
+        simulation.apply_config(&config);
+        let colony = &mut simulation.colonies[0];
+        // the ant went out through the gap beneath the wall on a detour
+        colony.add_ant(1, Pos::new(2, 1), AntState::Returner, &mut simulation.rng);
+        colony.ants.travel[0] = [(4, 4), (3, 4), (2, 4), (1, 4), (1, 3), (1, 2), (2, 1)]
+            .iter()
+            .map(|(x, y)| Pos::new(*x, *y))
+            .collect();
+
+        // and walks back the way it came instead of the shortest way home
+        for expected in [(1, 2), (1, 3), (1, 4), (2, 4), (3, 4), (4, 4)] {
+            simulation.step().unwrap();
+            assert!(simulation.colonies[0].ants.position[0] == Pos::new(expected.0, expected.1));
+        }
+        assert!(simulation.colonies[0].ants.state[0] != AntState::Returner);
+        assert_eq!(simulation.colonies[0].food, 1);
+    }
+
+    #[test]
+    fn seeker_blocked() {
+        // two obstacles touching at a corner
+        let map = Map::from_ascii(
+            "Corner",
+            "XXXXXX\nX....X\nX..X.X\nX.X..X\nXH...X\nXXXXXX\n",
+            &Config::default(),
+        )
+        .unwrap();
+        let target = |x, y, direction, speed| {
+            Simulation::seeker_target(&map, Pos::new(x, y), direction, speed)
+                .map(|pos| (pos.x, pos.y))
+        };
+        assert_eq!(target(2, 2, AntDirection::UpRight, 1), None);
+        assert!(!map.can_step(Pos::new(3, 3), Pos::new(2, 2)));
+        assert!(map.can_step(Pos::new(1, 1), Pos::new(2, 2)));
+        assert_eq!(target(1, 1, AntDirection::UpRight, 1), Some((2, 2)));
+        // fast ants do not jump the wall
+        assert_eq!(target(1, 2, AntDirection::Right, 3), None);
+        assert_eq!(target(1, 1, AntDirection::Right, 3), Some((4, 1)));
+    }
+
+    #[test]
+    fn descend_around_wall() {
+        let map = Map::from_ron(WALL_MAP, &Config::default()).unwrap();
+        let mut simulation = Simulation::new(map);
+        // straight towards the nest the wall is in the way
+        simulation.colonies[0].add_ant(1, Pos::new(2, 2), AntState::Noob, &mut simulation.rng);
+
+        let mut path = Vec::new();
+        for _ in 0..10 {
+            simulation.step().unwrap();
+            path.push(simulation.colonies[0].ants.position[0]);
+            if simulation.colonies[0].ants.state[0] != AntState::Noob {
+                break;
+            }
+        }
+        assert!(simulation.colonies[0].ants.state[0] != AntState::Noob);
+        assert!(path.iter().any(|pos| pos.y == 4));
+    }
+
+    #[test]
+    fn path_integration() {
+        let mut config = Config::default();
+        config.homing.set(2);
+        config.integration_noise.set(0);
+        let map = Map::from_ron(WALL_MAP, &config).unwrap();
+        let mut simulation = Simulation::new(map);
+        simulation.rng = ChaCha8Rng::seed_from_u64(0);
+        simulation.apply_config(&config);
+        let colony = &mut simulation.colonies[0];
+        colony.add_ant(1, Pos::new(1, 1), AntState::Noob, &mut simulation.rng);
+        // the ant knows exactly where the nest lies behind the wall
+        colony.ants.displacement[0] = Vector2::new(-6., -1.);
+
+        simulation.step().unwrap();
+        let ants = &simulation.colonies[0].ants;
+        assert_eq!(
+            ants.displacement[0],
+            Vector2::new(
+                ants.position[0].x as f32 - 7.,
+                ants.position[0].y as f32 - 2.
+            )
+        );
+        // stopped by the wall it searches around the nest until it finds the gap
+        for _ in 0..200 {
+            if simulation.colonies[0].ants.state[0] != AntState::Noob {
+                break;
+            }
+            simulation.step().unwrap();
+        }
+        assert!(simulation.colonies[0].ants.state[0] != AntState::Noob);
+        assert_eq!(simulation.colonies[0].ants.steps[0], 0);
+
+        // the error adds up with every step of the trip
+        let mut config = Config::default();
+        config.integration_noise.set(50);
+        let colony = &mut simulation.colonies[0];
+        colony.ants.position[0] = Pos::new(1, 1);
+        colony.ants.displacement[0] = Vector2::zeros();
+        let previous = [Pos::new(0, 1)];
+        let mut errors = Vec::new();
+        for step in 1..=100 {
+            Simulation::path_integration_system(
+                colony,
+                &simulation.map,
+                &config,
+                &mut simulation.rng,
+                &previous,
+            );
+            errors.push((colony.ants.displacement[0] - Vector2::new(step as f32, 0.)).norm());
+        }
+        assert!(errors[0] <= 0.5 * 2f32.sqrt());
+        assert!(errors[99] > errors[0]);
+    }
+
+    #[test]
+    fn search_missed_nest() {
+        let mut config = Config::default();
+        config.homing.set(2);
+        config.integration_noise.set(0);
+        let map = Map::from_ron(OPEN_MAP, &config).unwrap();
+        let mut simulation = Simulation::new(map);
+        simulation.rng = ChaCha8Rng::seed_from_u64(0);
+        simulation.apply_config(&config);
+        let colony = &mut simulation.colonies[0];
+        colony.add_ant(1, Pos::new(5, 10), AntState::Noob, &mut simulation.rng);
+        // the errors of a long trip place the nest fifteen tiles short of it
+        colony.ants.displacement[0] = Vector2::new(-5., 0.);
+
+        let estimate = Pos::new(10, 10);
+        for step in 1..=60 {
+            simulation.step().unwrap();
+            let ants = &simulation.colonies[0].ants;
+            if step == 5 {
+                assert!(ants.position[0] == estimate);
+            }
+            if step > 5 {
+                assert!(simulation.map.dist(estimate, ants.position[0]) <= 9.);
+            }
+        }
+        let ants = &simulation.colonies[0].ants;
+        assert!(ants.state[0] == AntState::Noob);
+        assert_eq!(ants.steps[0], 55);
+        assert!(ants.position[0] != estimate);
+    }
+
+    #[test]
+    fn colony_economy() {
+        let mut config = Config::default();
+        config.ant_energy.set(10);
+        config.food_energy.set(4);
+        config.ant_cost.set(2);
+        config.food_reserve.set(1);
+        let map = Map::from_ron(WALL_MAP, &config).unwrap();
+        let mut simulation = Simulation::new(map);
+        simulation.apply_config(&config);
+        let colony = &mut simulation.colonies[0];
+        colony.add_ant(1, Pos::new(4, 4), AntState::Seeker, &mut simulation.rng);
+        // five steps around the wall away from the nest
+        colony.add_ant(1, Pos::new(0, 0), AntState::Seeker, &mut simulation.rng);
+        colony.ants.hunger[0] = 9;
+        colony.ants.hunger[1] = 4;
+        colony.store = 5;
+
+        // only whole pieces of food are eaten, the far ant turns back
+        Simulation::energy_system(colony, &simulation.map, &config);
+        assert_eq!(colony.ants.hunger[0], 2);
+        assert_eq!(colony.store, 3);
+        assert!(colony.ants.state[1] == AntState::Noob);
+
+        colony.ants.hunger[1] = 9;
+        Simulation::energy_system(colony, &simulation.map, &config);
+        assert_eq!(colony.ants.state.len(), 1);
+        assert_eq!(colony.died, 1);
+
+        Simulation::brood_system(colony, &simulation.map, &config, &mut simulation.rng);
+        assert_eq!(colony.ants.state.len(), 2);
+        assert_eq!((colony.store, colony.born), (1, 1));
+        // the reserve is kept
+        Simulation::brood_system(colony, &simulation.map, &config, &mut simulation.rng);
+        assert_eq!(colony.ants.state.len(), 2);
+
+        // by default ants neither starve nor grow old and none are raised
+        let config = Config::default();
+        colony.ants.hunger[0] = 100_000;
+        colony.ants.age[0] = 100_000;
+        colony.store = 100;
+        Simulation::energy_system(colony, &simulation.map, &config);
+        Simulation::brood_system(colony, &simulation.map, &config, &mut simulation.rng);
+        assert_eq!(colony.ants.state.len(), 2);
+        assert_eq!(colony.store, 100);
+    }
+
+    #[test]
+    fn colony_per_nest() {
+        let mut config = Config::default();
+        config.colonies.set(2);
+        let mut simulation = Simulation::new(Map::new("Map", &config));
+        assert_eq!(simulation.colonies.len(), 2);
+
+        simulation.spwan_ant();
+        simulation.step().unwrap();
+        for colony in simulation.colonies.iter() {
+            assert_eq!(colony.ants.position.len(), 1);
+            assert!(colony.nest == simulation.map.nests[colony.id]);
+        }
+        assert_eq!(simulation.history.colony_ants[0].len(), 2);
+    }
+
+    #[test]
+    fn save_and_resume() {
+        let mut config = Config::default();
+        config.seed.set(7);
+        let mut simulation = Simulation::new(Map::new("Map", &config));
+        simulation.reset(config).unwrap();
+        for _ in 0..5 {
+            simulation.spwan_ant();
+        }
+        for _ in 0..30 {
+            simulation.step().unwrap();
+        }
+
+        let path = std::env::temp_dir().join("ant_sim_save_and_resume.ron");
+        simulation.save(&path).unwrap();
+        let mut resumed = Simulation::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(resumed.paused);
+
+        // the same random decisions are taken after resuming
+        for _ in 0..30 {
+            simulation.step().unwrap();
+            resumed.step().unwrap();
+        }
+        let positions = |s: &Simulation| {
+            s.colonies[0]
+                .ants
+                .position
+                .iter()
+                .map(|p| (p.x, p.y))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(positions(&simulation), positions(&resumed));
+        assert_eq!(simulation.history.food_total, resumed.history.food_total);
+        assert_eq!(resumed.config().seed.val(), 7);
+    }
+
+    #[test]
+    fn saved_config() {
+        let mut config = Config::default();
+        config.seed.set(7);
+        let contents = ron::ser::to_string(&SavedConfig::new(&config)).unwrap();
+        let mut loaded = Config::default();
+        ron::from_str::<SavedConfig>(&contents)
+            .unwrap()
+            .apply(&mut loaded);
+        assert_eq!(loaded.seed.val(), 7);
+
+        // matched by name whatever the order
+        let mut loaded = Config::default();
+        ron::from_str::<SavedConfig>(r#"[("Seed", 3), ("Ph drop", 12)]"#)
+            .unwrap()
+            .apply(&mut loaded);
+        assert_eq!((loaded.seed.val(), loaded.ph_drop.val()), (3, 12));
+    }
+
+    #[test]
+    fn live_config() {
+        let mut config = Config::default();
+        let mut simulation = Simulation::new(Map::new("Map", &config));
+        simulation.step().unwrap();
+
+        config.ph_drop.set(50);
+        config.map_width.set(60);
+        simulation.apply_config(&config);
+        assert_eq!(simulation.config().ph_drop.val(), 50);
+        assert_eq!(simulation.config().map_width.val(), 115);
+        assert!(simulation.requires_reset(&config, 4));
+        assert_eq!(simulation.history.config_changes.len(), 1);
+        assert_eq!(simulation.history.config_changes[0].step, 1);
+
+        // the new map is built with the new config
+        simulation.reset(config).unwrap();
+        assert_eq!(*simulation.map.width(), 60);
+        assert!(!simulation.requires_reset(&config, 4));
+        let change = simulation.history.config_changes.last().unwrap();
+        assert_eq!((change.old, change.new), (115, 60));
+    }
+}
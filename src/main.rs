@@ -20,6 +20,7 @@ use crossterm::{
 use std::{
     borrow::Borrow,
     io::{self, Write},
+    path::PathBuf,
     rc::Rc,
     time::{Duration, Instant},
 };
@@ -34,10 +35,14 @@ use ui::UI;
 
 fn main() -> Result<()> {
     setup_logging()?;
+
+    // optional map file, e.g. `cargo run -- assets/map.ron`
+    let map_path = std::env::args().nth(1).map(PathBuf::from);
+    let mut app = App::new(true, map_path)?;
+
     setup_terminal()?;
 
     let mut terminal = start_terminal(io::stdout())?;
-    let mut app = App::new(true);
     let mut ui = UI::new();
 
     app.start()?;
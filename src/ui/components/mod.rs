@@ -1,4 +1,4 @@
-pub mod config_vars;
-pub mod disc_stats;
-pub mod player_list;
-pub mod scorecard;
+pub mod config_vars;
+pub mod disc_stats;
+pub mod player_list;
+pub mod scorecard;
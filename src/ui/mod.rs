@@ -1,6 +1,6 @@
-pub mod components;
-pub mod tabs;
-pub mod ui;
-mod widgets;
-
-pub use self::ui::UI;
+pub mod components;
+pub mod tabs;
+pub mod ui;
+mod widgets;
+
+pub use self::ui::UI;
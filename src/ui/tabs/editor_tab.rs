@@ -0,0 +1,123 @@
+use anyhow::Result;
+use tui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+
+use crate::{
+    app::App,
+    core::map::map::{Pos, TileType},
+    keys::get_hint,
+    style::SharedTheme,
+    ui::{tabs::simulation::ANT_GLYPHS, widgets::DrawableComponent},
+    utils::renderer::{PrintRenderer, Renderer},
+};
+
+/// Map file the editor writes to.
+pub const EDITOR_MAP_PATH: &str = "assets/editor_map.ron";
+/// Text grid the editor exports to.
+pub const EDITOR_ASCII_PATH: &str = "assets/editor_map.txt";
+
+pub struct EditorTab {
+    visible: bool,
+    pub cursor: Pos,
+    /// Colony whose nest is moved.
+    pub colony: usize,
+    /// Result of the last save.
+    pub status: String,
+}
+
+impl EditorTab {
+    pub fn new(theme: SharedTheme) -> Self {
+        Self {
+            visible: false,
+            cursor: Pos::new(0, 0),
+            colony: 0,
+            status: String::new(),
+        }
+    }
+}
+
+impl DrawableComponent for EditorTab {
+    fn draw<B: tui::backend::Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+        app: &App,
+    ) -> Result<()> {
+        let main_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Percentage(100)].as_ref())
+            .split(rect);
+
+        let keys = &app.key_config;
+        let tile = if app.simulation.map.in_bounds(self.cursor) {
+            let tile = app.simulation.map.get(self.cursor);
+            format!("{:?} on {:?}", tile.tile_type, tile.terrain)
+        } else {
+            String::new()
+        };
+        let help = Paragraph::new(Spans::from(vec![
+            Span::raw(format!(
+                "({}, {}) {}  |  ",
+                self.cursor.x, self.cursor.y, tile
+            )),
+            Span::raw(format!(
+                "[{}] food [{}] obstacle [{}] empty [{}] terrain [{}] nest {} [{}] colony [{}] save [{}] export  ",
+                get_hint(keys.paint_food),
+                get_hint(keys.paint_obstacle),
+                get_hint(keys.paint_empty),
+                get_hint(keys.paint_terrain),
+                get_hint(keys.move_nest),
+                self.colony + 1,
+                get_hint(keys.next_colony),
+                get_hint(keys.save_map),
+                get_hint(keys.export_map),
+            )),
+            Span::styled(self.status.as_str(), Style::default().fg(Color::Yellow)),
+        ]))
+        .block(Block::default().borders(Borders::ALL).title("Editor"));
+
+        let map_view = Block::default()
+            .title(format!("Map ({})", app.simulation.map.boundary.name()))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::White))
+            .border_type(BorderType::Plain);
+
+        let renderer = PrintRenderer::new('H', 'F', 'O', ANT_GLYPHS);
+        let final_string = renderer.render(
+            &app.simulation.map,
+            &app.simulation.colonies,
+            rect.width.into(),
+            rect.height.into(),
+        );
+
+        // highlight the tile under the cursor
+        let mut text: Vec<Spans> = Vec::new();
+        for (y, s) in final_string.iter().enumerate() {
+            if y as i32 != self.cursor.y {
+                text.push(Spans::from(vec![Span::raw(s.to_owned())]));
+                continue;
+            }
+            let chars: Vec<char> = s.chars().collect();
+            let x = (self.cursor.x as usize).min(chars.len());
+            let cursor: String = chars.get(x).map_or(' ', |c| *c).to_string();
+            text.push(Spans::from(vec![
+                Span::raw(chars[..x].iter().collect::<String>()),
+                Span::styled(cursor, Style::default().add_modifier(Modifier::REVERSED)),
+                Span::raw(chars[(x + 1).min(chars.len())..].iter().collect::<String>()),
+            ]));
+        }
+        let para = Paragraph::new(text)
+            .block(map_view)
+            .style(Style::default().fg(Color::White));
+
+        f.render_widget(help, main_chunks[0]);
+        f.render_widget(para, main_chunks[1]);
+
+        Ok(())
+    }
+}
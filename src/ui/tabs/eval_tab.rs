@@ -9,9 +9,9 @@ use tui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Style},
     symbols,
-    text::Span,
+    text::{Span, Spans},
     widgets::GraphType,
-    widgets::{Axis, Block, Borders, Chart, Dataset, Sparkline},
+    widgets::{Axis, Block, Borders, Chart, Dataset, Paragraph, Sparkline},
     Frame,
 };
 
@@ -34,7 +34,15 @@ impl DrawableComponent for EvalTab {
     ) -> Result<()> {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Percentage(30), Constraint::Percentage(30)].as_ref())
+            .constraints(
+                [
+                    Constraint::Percentage(25),
+                    Constraint::Percentage(25),
+                    Constraint::Percentage(25),
+                    Constraint::Percentage(25),
+                ]
+                .as_ref(),
+            )
             .split(rect);
 
         let y_food_bounds: [f64; 2] = [
@@ -89,6 +97,15 @@ impl DrawableComponent for EvalTab {
             .map(|(s, f)| (*s as f64, *f as f64))
             .collect();
 
+        let store_data: Vec<(f64, f64)> = app
+            .simulation
+            .history
+            .step
+            .iter()
+            .zip(&app.simulation.history.colony_store)
+            .map(|(s, f)| (*s as f64, f.iter().sum::<i32>() as f64))
+            .collect();
+
         let food_step_data: Vec<(f64, f64)> = app
             .simulation
             .history
@@ -134,6 +151,91 @@ impl DrawableComponent for EvalTab {
             .map(|(s, f)| (*s as f64, f[3] as f64))
             .collect();
 
+        let colony_count = app
+            .simulation
+            .history
+            .colony_food
+            .last()
+            .map(|food| food.len())
+            .unwrap_or(0);
+
+        let colony_data: Vec<Vec<(f64, f64)>> = (0..colony_count)
+            .map(|i| {
+                app.simulation
+                    .history
+                    .step
+                    .iter()
+                    .zip(&app.simulation.history.colony_food)
+                    .map(|(s, f)| (*s as f64, *f.get(i).unwrap_or(&0) as f64))
+                    .collect()
+            })
+            .collect();
+
+        let source_count = app
+            .simulation
+            .history
+            .food_sources
+            .last()
+            .map(|sources| sources.len())
+            .unwrap_or(0);
+
+        let source_data: Vec<Vec<(f64, f64)>> = (0..source_count)
+            .map(|i| {
+                app.simulation
+                    .history
+                    .step
+                    .iter()
+                    .zip(&app.simulation.history.food_sources)
+                    .map(|(s, f)| (*s as f64, *f.get(i).unwrap_or(&0) as f64))
+                    .collect()
+            })
+            .collect();
+
+        let y_source_bounds: [f64; 2] = [
+            0.,
+            app.simulation
+                .history
+                .food_sources
+                .iter()
+                .flatten()
+                .max()
+                .cloned()
+                .unwrap_or(10) as f64,
+        ];
+
+        let nest_ph_data: Vec<Vec<(f64, f64)>> = (0..colony_count)
+            .map(|i| {
+                app.simulation
+                    .history
+                    .step
+                    .iter()
+                    .zip(&app.simulation.history.nest_ph)
+                    .map(|(s, ph)| (*s as f64, *ph.get(i).unwrap_or(&0.) as f64))
+                    .collect()
+            })
+            .collect();
+
+        // ants start as followers above the threshold
+        let min_ph_c = app.simulation.config().min_ph_c.val() as f64;
+        let threshold_data: Vec<(f64, f64)> = app
+            .simulation
+            .history
+            .step
+            .iter()
+            .map(|s| (*s as f64, min_ph_c))
+            .collect();
+
+        let y_nest_ph_bounds: [f64; 2] = [
+            0.,
+            app.simulation
+                .history
+                .nest_ph
+                .iter()
+                .flatten()
+                .cloned()
+                .fold(min_ph_c as f32 * 2., f32::max) as f64,
+        ];
+
         let slice_bounds = if food_step_data.len() > 100 {
             food_step_data.len() - 100
         } else {
@@ -175,52 +277,117 @@ impl DrawableComponent for EvalTab {
             ant_labels.push(i.to_string());
         }
 
+        let nest_ph_labels = vec![String::from("0"), (y_nest_ph_bounds[1] as i32).to_string()];
+
+        let source_labels = vec![String::from("0"), (y_source_bounds[1] as i32).to_string()];
+
         for i in (step_bounds[0] as i32..step_bounds[1] as i32).step_by(20) {
             step_labels.push(i.to_string());
         }
 
-        let food_datasets = vec![
+        let mut food_datasets = vec![
             Dataset::default()
                 .name("Total food")
                 .marker(symbols::Marker::Braille)
                 .graph_type(GraphType::Line)
                 .style(Style::default().fg(Color::Yellow))
-                .data(&total_food_data[slice_bounds..]),
+                .data(last_steps(&total_food_data, slice_bounds)),
             Dataset::default()
                 .name("Step food")
                 .marker(symbols::Marker::Braille)
                 .graph_type(GraphType::Line)
                 .style(Style::default().fg(Color::Red))
-                .data(&food_step_data[slice_bounds..]),
+                .data(last_steps(&food_step_data, slice_bounds)),
+            Dataset::default()
+                .name("Stored food")
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(Color::Gray))
+                .data(last_steps(&store_data, slice_bounds)),
         ];
 
+        // the colonies only get their own line when they compete
+        let colony_colors = [Color::Blue, Color::Green, Color::Magenta, Color::Cyan];
+        let colony_names: Vec<String> = (0..colony_count)
+            .map(|i| format!("Colony {}", i + 1))
+            .collect();
+        if colony_count > 1 {
+            food_datasets.extend(colony_data.iter().enumerate().map(|(i, data)| {
+                Dataset::default()
+                    .name(colony_names[i].as_str())
+                    .marker(symbols::Marker::Braille)
+                    .graph_type(GraphType::Line)
+                    .style(Style::default().fg(colony_colors[i % colony_colors.len()]))
+                    .data(last_steps(data, slice_bounds))
+            }));
+        }
+
         let ant_datasets = vec![
             Dataset::default()
                 .name("Seeker")
                 .marker(symbols::Marker::Braille)
                 .graph_type(GraphType::Line)
                 .style(Style::default().fg(Color::Blue))
-                .data(&seeker_data[slice_bounds..]),
+                .data(last_steps(&seeker_data, slice_bounds)),
             Dataset::default()
                 .name("Returner")
                 .marker(symbols::Marker::Braille)
                 .graph_type(GraphType::Line)
                 .style(Style::default().fg(Color::Red))
-                .data(&returner_data[slice_bounds..]),
+                .data(last_steps(&returner_data, slice_bounds)),
             Dataset::default()
                 .name("Follower")
                 .marker(symbols::Marker::Braille)
                 .graph_type(GraphType::Line)
                 .style(Style::default().fg(Color::Green))
-                .data(&follower_data[slice_bounds..]),
+                .data(last_steps(&follower_data, slice_bounds)),
             Dataset::default()
                 .name("Noobs")
                 .marker(symbols::Marker::Braille)
                 .graph_type(GraphType::Line)
                 .style(Style::default().fg(Color::Yellow))
-                .data(&noob_data[slice_bounds..]),
+                .data(last_steps(&noob_data, slice_bounds)),
         ];
 
+        let mut nest_ph_datasets = vec![Dataset::default()
+            .name("Follower threshold")
+            .marker(symbols::Marker::Dot)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::DarkGray))
+            .data(last_steps(&threshold_data, slice_bounds))];
+        nest_ph_datasets.extend(nest_ph_data.iter().enumerate().map(|(i, data)| {
+            Dataset::default()
+                .name(colony_names[i].as_str())
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(colony_colors[i % colony_colors.len()]))
+                .data(last_steps(data, slice_bounds))
+        }));
+
+        let source_colors = [
+            Color::Yellow,
+            Color::Red,
+            Color::Green,
+            Color::Blue,
+            Color::Magenta,
+            Color::Cyan,
+        ];
+        let source_names: Vec<String> = (0..source_count)
+            .map(|i| format!("Source {}", i + 1))
+            .collect();
+        let source_datasets = source_data
+            .iter()
+            .enumerate()
+            .map(|(i, data)| {
+                Dataset::default()
+                    .name(source_names[i].as_str())
+                    .marker(symbols::Marker::Braille)
+                    .graph_type(GraphType::Line)
+                    .style(Style::default().fg(source_colors[i % source_colors.len()]))
+                    .data(last_steps(data, slice_bounds))
+            })
+            .collect();
+
         let food_chart = Chart::new(food_datasets)
             .block(
                 Block::default()
@@ -263,9 +430,84 @@ impl DrawableComponent for EvalTab {
                     .style(Style::default().fg(Color::White)),
             );
 
+        let source_chart = Chart::new(source_datasets)
+            .block(
+                Block::default()
+                    .title("Food left per source")
+                    .borders(Borders::ALL),
+            )
+            .x_axis(
+                Axis::default()
+                    .title("steps")
+                    .labels(step_labels.iter().cloned().map(Span::from).collect())
+                    .bounds(step_bounds)
+                    .style(Style::default().fg(Color::White)),
+            )
+            .y_axis(
+                Axis::default()
+                    .title("Food left")
+                    .labels(source_labels.iter().cloned().map(Span::from).collect())
+                    .bounds(y_source_bounds)
+                    .style(Style::default().fg(Color::White)),
+            );
+
+        let nest_ph_chart = Chart::new(nest_ph_datasets)
+            .block(
+                Block::default()
+                    .title("Pheromone around the nest")
+                    .borders(Borders::ALL),
+            )
+            .x_axis(
+                Axis::default()
+                    .title("steps")
+                    .labels(step_labels.iter().cloned().map(Span::from).collect())
+                    .bounds(step_bounds)
+                    .style(Style::default().fg(Color::White)),
+            )
+            .y_axis(
+                Axis::default()
+                    .title("Nest pheromone")
+                    .labels(nest_ph_labels.iter().cloned().map(Span::from).collect())
+                    .bounds(y_nest_ph_bounds)
+                    .style(Style::default().fg(Color::White)),
+            );
+
+        // the latest config changes next to the food sources, newest first
+        let bottom_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(70), Constraint::Percentage(30)].as_ref())
+            .split(chunks[3]);
+        let changes: Vec<Spans> = app
+            .simulation
+            .history
+            .config_changes
+            .iter()
+            .rev()
+            .take(bottom_chunks[1].height as usize)
+            .map(|change| {
+                Spans::from(format!(
+                    "{}: {} {} -> {}",
+                    change.step, change.name, change.old, change.new
+                ))
+            })
+            .collect();
+        let changes = Paragraph::new(changes).block(
+            Block::default()
+                .title("Config changes")
+                .borders(Borders::ALL),
+        );
+
         f.render_widget(food_chart, chunks[0]);
         f.render_widget(ant_chart, chunks[1]);
+        f.render_widget(nest_ph_chart, chunks[2]);
+        f.render_widget(source_chart, bottom_chunks[0]);
+        f.render_widget(changes, bottom_chunks[1]);
 
         Ok(())
     }
 }
+
+/// Points of a series from `start` on, all of them if the series is shorter.
+fn last_steps(data: &[(f64, f64)], start: usize) -> &[(f64, f64)] {
+    &data[start.min(data.len())..]
+}
@@ -1,7 +1,9 @@
-pub mod config_tab;
-pub mod eval_tab;
-pub mod simulation;
-
-pub use config_tab::ConfigTab;
-pub use eval_tab::EvalTab;
-pub use simulation::Simulation;
+pub mod config_tab;
+pub mod editor_tab;
+pub mod eval_tab;
+pub mod simulation;
+
+pub use config_tab::ConfigTab;
+pub use editor_tab::EditorTab;
+pub use eval_tab::EvalTab;
+pub use simulation::Simulation;
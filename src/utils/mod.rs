@@ -1,23 +1,38 @@
 use nalgebra::Vector2;
-use rand::{
-    distributions::Standard,
-    prelude::{Distribution, SliceRandom, ThreadRng},
-    Rng,
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::{
+    f32::consts::{FRAC_PI_2, FRAC_PI_4, PI},
+    path::Ancestors,
 };
-use std::{f64::consts::PI, path::Ancestors};
+
+use crate::config::Config;
 
 pub mod func;
 pub mod renderer;
 
-pub const FROM_U_D: [AntDirection; 2] = [AntDirection::Right, AntDirection::Left];
-pub const FROM_R_L: [AntDirection; 2] = [AntDirection::Up, AntDirection::Down];
+/// All directions, counterclockwise in steps of 45° starting at `Right`.
+pub const DIRECTIONS: [AntDirection; 8] = [
+    AntDirection::Right,
+    AntDirection::UpRight,
+    AntDirection::Up,
+    AntDirection::UpLeft,
+    AntDirection::Left,
+    AntDirection::DownLeft,
+    AntDirection::Down,
+    AntDirection::DownRight,
+];
 
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AntDirection {
     Up,
     Down,
     Right,
     Left,
+    UpRight,
+    UpLeft,
+    DownRight,
+    DownLeft,
 }
 
 impl AntDirection {
@@ -27,24 +42,103 @@ impl AntDirection {
             AntDirection::Down => Vector2::new(0, -1),
             AntDirection::Right => Vector2::new(1, 0),
             AntDirection::Left => Vector2::new(-1, 0),
+            AntDirection::UpRight => Vector2::new(1, 1),
+            AntDirection::UpLeft => Vector2::new(-1, 1),
+            AntDirection::DownRight => Vector2::new(1, -1),
+            AntDirection::DownLeft => Vector2::new(-1, -1),
+        }
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        self.vec().x != 0 && self.vec().y != 0
+    }
+
+    /// Heading of the direction in radians, counterclockwise from `Right`.
+    pub fn angle(&self) -> f32 {
+        let index = DIRECTIONS.iter().position(|d| d == self).unwrap_or(0);
+        index as f32 * FRAC_PI_4
+    }
+
+    /// The closest of the eight directions to a heading.
+    pub fn from_angle(angle: f32) -> AntDirection {
+        DIRECTIONS[((angle / FRAC_PI_4).round() as i32).rem_euclid(8) as usize]
+    }
+}
+
+/// How seekers walk, selected via `Config::movement`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Movement {
+    /// Up, down, left and right, turning by 90°.
+    Orthogonal,
+    /// Diagonals as well, turning by 45°.
+    Diagonal,
+    /// A continuous heading turning by small random angles, quantized to the eight directions.
+    Continuous,
+}
+
+impl Movement {
+    pub fn from_config(config: &Config) -> Self {
+        match config.movement.val() {
+            0 => Movement::Orthogonal,
+            1 => Movement::Diagonal,
+            _ => Movement::Continuous,
         }
     }
 
-    pub fn random_turn(self, rng: &mut ThreadRng) -> AntDirection {
+    /// Directions an ant may take.
+    pub fn directions(&self) -> impl Iterator<Item = AntDirection> + '_ {
+        DIRECTIONS
+            .iter()
+            .cloned()
+            .filter(move |d| *self != Movement::Orthogonal || !d.is_diagonal())
+    }
+
+    /// Grid direction of a heading.
+    pub fn quantize(&self, heading: f32) -> AntDirection {
         match self {
-            AntDirection::Up | AntDirection::Down => *FROM_U_D.choose(rng).unwrap(),
-            AntDirection::Right | AntDirection::Left => *FROM_R_L.choose(rng).unwrap(),
+            Movement::Orthogonal => {
+                DIRECTIONS[2 * ((heading / FRAC_PI_2).round() as i32).rem_euclid(4) as usize]
+            }
+            _ => AntDirection::from_angle(heading),
         }
     }
+
+    /// Turns the heading left or right, by up to `max_angle` degrees for a continuous heading.
+    pub fn turn<R: Rng>(&self, heading: f32, max_angle: i32, rng: &mut R) -> f32 {
+        let side = if rng.gen() { 1. } else { -1. };
+        let angle = match self {
+            Movement::Orthogonal => FRAC_PI_2,
+            Movement::Diagonal => FRAC_PI_4,
+            Movement::Continuous => (max_angle as f32).to_radians() * rng.gen::<f32>(),
+        };
+        (heading + side * angle).rem_euclid(2. * PI)
+    }
 }
 
-impl Distribution<AntDirection> for Standard {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> AntDirection {
-        match rng.gen_range(0..4) {
-            0 => AntDirection::Up,
-            1 => AntDirection::Down,
-            2 => AntDirection::Left,
-            _ => AntDirection::Right,
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::f32::consts::PI;
+
+    use super::{AntDirection, Movement};
+
+    #[test]
+    fn quantize_heading() {
+        assert_eq!(AntDirection::from_angle(0.3), AntDirection::Right);
+        assert_eq!(AntDirection::from_angle(0.5), AntDirection::UpRight);
+        assert_eq!(AntDirection::from_angle(-0.5), AntDirection::DownRight);
+        assert_eq!(Movement::Orthogonal.quantize(0.5), AntDirection::Right);
+        assert_eq!(Movement::Orthogonal.quantize(1.2), AntDirection::Up);
+        assert_eq!(Movement::Orthogonal.directions().count(), 4);
+        assert_eq!(Movement::Diagonal.directions().count(), 8);
+
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        for _ in 0..20 {
+            let heading = Movement::Continuous.turn(PI, 30, &mut rng);
+            assert!((heading - PI).abs() <= 30f32.to_radians() + 1e-5);
+            let heading = Movement::Diagonal.turn(PI, 30, &mut rng);
+            assert!(((heading - PI).abs() - PI / 4.).abs() < 1e-5);
         }
     }
 }